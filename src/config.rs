@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::rules::Rule;
+use crate::thinbasic_script::Severity;
+
+/// Name of the configuration file looked up next to the analysed script and in its parents
+pub const FILE_NAME: &str = "thinclippy.toml";
+
+/// Project configuration, as read from thinclippy.toml
+///
+/// ```toml
+/// fail-level = "warning"
+///
+/// [rules.alias-syntax]
+/// severity = "warning"
+///
+/// [rules.TC0003]
+/// enabled = true
+/// max-length = 120
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(rename = "fail-level")]
+    pub fail_level: Option<Severity>,
+
+    /// Settings of individual rules, keyed by rule code or rule name
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RuleConfig {
+    pub enabled: Option<bool>,
+    pub severity: Option<Severity>,
+
+    /// Rule specific options, such as max-length
+    #[serde(flatten)]
+    pub options: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+        let config: Config =
+            toml::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+
+        Ok(config)
+    }
+
+    /// Looks for thinclippy.toml in the directory of the script and then in all its parents
+    pub fn find(script_file: &str) -> Option<PathBuf> {
+        let script_path = Path::new(script_file).canonicalize().ok()?;
+
+        script_path
+            .ancestors()
+            .skip(1)
+            .map(|directory| directory.join(FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Settings for the given rule, if any were specified
+    pub fn rule(&self, rule: &dyn Rule) -> Option<&RuleConfig> {
+        self.rules
+            .get(rule.id())
+            .or_else(|| self.rules.get(rule.name()))
+    }
+
+    /// Checks all the rule sections refer to an existing rule
+    pub fn validate(&self, rules: &[Box<dyn Rule>]) -> Result<(), String> {
+        for key in self.rules.keys() {
+            if !rules
+                .iter()
+                .any(|rule| rule.id() == key || rule.name() == key)
+            {
+                return Err(format!("Unknown rule '{}' in configuration", key));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RuleConfig {
+    /// Fails on options the rule does not know, which are most likely typos
+    pub fn check_options(&self, known: &[&str]) -> Result<(), String> {
+        match self
+            .options
+            .keys()
+            .find(|name| !known.contains(&name.as_str()))
+        {
+            None => Ok(()),
+            Some(name) if known.is_empty() => Err(format!(
+                "Unknown option '{}', expected enabled or severity",
+                name
+            )),
+            Some(name) => Err(format!(
+                "Unknown option '{}', expected enabled, severity or {}",
+                name,
+                known.join(", ")
+            )),
+        }
+    }
+
+    pub fn option_u32(&self, name: &str) -> Result<Option<u32>, String> {
+        match self.options.get(name) {
+            None => Ok(None),
+            Some(toml::Value::Integer(value)) if *value > 0 && *value <= i64::from(u32::MAX) => {
+                Ok(Some(*value as u32))
+            }
+            Some(_) => Err(format!("Option '{}' must be a positive number", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, FILE_NAME};
+    use crate::thinbasic_script::Severity;
+    use std::fs;
+
+    #[test]
+    fn parses_rule_sections() {
+        let config: Config = toml::from_str(
+            r#"
+            fail-level = "warning"
+
+            [rules.alias-syntax]
+            enabled = false
+
+            [rules.TC0003]
+            severity = "hint"
+            max-length = 100
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.fail_level, Some(Severity::Warning));
+        assert_eq!(config.rules["alias-syntax"].enabled, Some(false));
+        assert_eq!(config.rules["TC0003"].severity, Some(Severity::Hint));
+        assert_eq!(
+            config.rules["TC0003"].option_u32("max-length"),
+            Ok(Some(100))
+        );
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        let config: Config = toml::from_str(
+            r#"
+            [rules.alias-syntax]
+            enable = false
+
+            [rules.TC0003]
+            max-length = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.rules["alias-syntax"].check_options(&[]),
+            Err("Unknown option 'enable', expected enabled or severity".to_string())
+        );
+        assert_eq!(
+            config.rules["TC0003"].check_options(&["max-length"]),
+            Ok(())
+        );
+        assert_eq!(
+            config.rules["TC0003"].option_u32("max-length"),
+            Err("Option 'max-length' must be a positive number".to_string())
+        );
+    }
+
+    #[test]
+    fn file_is_found_in_parent_directories() {
+        let root = std::env::temp_dir().join(format!("thinclippy-config-{}", std::process::id()));
+        let nested = root.join("project").join("scripts");
+        fs::create_dir_all(&nested).unwrap();
+
+        let script = nested.join("main.tbasic");
+        fs::write(&script, "").unwrap();
+
+        let found = Config::find(script.to_str().unwrap());
+        fs::write(root.join(FILE_NAME), "").unwrap();
+        let found_in_root = Config::find(script.to_str().unwrap());
+        fs::write(nested.join(FILE_NAME), "").unwrap();
+        let found_nearest = Config::find(script.to_str().unwrap());
+
+        let root = root.canonicalize().unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        // Directories above the temporary one are out of the test's control
+        assert!(found.is_none_or(|path| !path.starts_with(&root)));
+        assert_eq!(found_in_root, Some(root.join(FILE_NAME)));
+        assert_eq!(
+            found_nearest,
+            Some(root.join("project").join("scripts").join(FILE_NAME))
+        );
+    }
+}
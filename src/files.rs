@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+
+use crate::report::ScriptFailure;
+
+/// Script extensions analysed when searching directories, unless specified otherwise
+pub const DEFAULT_EXTENSIONS: [&str; 2] = ["tbasic", "tbasicc"];
+
+/// Scripts found for analysis, together with the places which could not be searched
+pub struct Collected {
+    pub script_files: Vec<String>,
+    pub failures: Vec<ScriptFailure>,
+}
+
+/// Expands the paths given on command line to the list of scripts to analyse
+///
+/// Files are taken as they are, directories are searched recursively for scripts with
+/// one of the extensions and glob patterns are expanded to files and directories.
+/// Anything matching one of the exclude patterns is skipped. Directories which cannot
+/// be read are recorded as failures, while the search goes on.
+pub fn collect(
+    paths: &[String],
+    extensions: &[String],
+    excludes: &[String],
+) -> Result<Collected, String> {
+    let excludes = excludes
+        .iter()
+        .map(|exclude| {
+            Pattern::new(exclude)
+                .map_err(|e| format!("Invalid exclude pattern '{}': {}", exclude, e))
+        })
+        .collect::<Result<Vec<Pattern>, String>>()?;
+
+    let mut collected: Vec<PathBuf> = vec![];
+    let mut failures: Vec<ScriptFailure> = vec![];
+
+    for path in paths {
+        if is_glob(path) {
+            let entries =
+                glob::glob(path).map_err(|e| format!("Invalid pattern '{}': {}", path, e))?;
+
+            let mut matched = false;
+
+            for entry in entries {
+                matched = true;
+
+                match entry {
+                    Ok(entry) => {
+                        add_path(&entry, extensions, &excludes, &mut collected, &mut failures)
+                    }
+                    Err(e) => failures.push(read_failure(e.path(), e.error())),
+                }
+            }
+
+            if !matched {
+                return Err(format!("No files match the pattern '{}'", path));
+            }
+        } else {
+            add_path(
+                Path::new(path),
+                extensions,
+                &excludes,
+                &mut collected,
+                &mut failures,
+            );
+        }
+    }
+
+    let mut script_files: Vec<String> = vec![];
+
+    for path in collected {
+        let path = path.to_string_lossy().into_owned();
+
+        if !script_files.contains(&path) {
+            script_files.push(path);
+        }
+    }
+
+    Ok(Collected {
+        script_files,
+        failures,
+    })
+}
+
+fn read_failure(path: &Path, error: &std::io::Error) -> ScriptFailure {
+    ScriptFailure {
+        script_file: path.to_string_lossy().into_owned(),
+        message: format!("{}: could not be read, {}", path.display(), error),
+    }
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+fn is_excluded(path: &Path, excludes: &[Pattern]) -> bool {
+    let file_name = path.file_name().map(|name| name.to_string_lossy());
+
+    excludes.iter().any(|exclude| {
+        exclude.matches_path(path) || file_name.as_ref().is_some_and(|name| exclude.matches(name))
+    })
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    let extension = match path.extension() {
+        Some(extension) => extension.to_string_lossy(),
+        None => return false,
+    };
+
+    if extensions.is_empty() {
+        DEFAULT_EXTENSIONS
+            .iter()
+            .any(|default| extension.eq_ignore_ascii_case(default))
+    } else {
+        extensions
+            .iter()
+            .any(|wanted| extension.eq_ignore_ascii_case(wanted.trim_start_matches('.')))
+    }
+}
+
+fn add_path(
+    path: &Path,
+    extensions: &[String],
+    excludes: &[Pattern],
+    collected: &mut Vec<PathBuf>,
+    failures: &mut Vec<ScriptFailure>,
+) {
+    if is_excluded(path, excludes) {
+        return;
+    }
+
+    if !path.is_dir() {
+        // Missing files are passed on, so they get reported as input errors
+        collected.push(path.to_path_buf());
+        return;
+    }
+
+    // Entry type is not resolved through symbolic links, so linked directories
+    // cannot lead the recursion back to their parents
+    let directory = match fs::read_dir(path) {
+        Ok(directory) => directory,
+        Err(e) => {
+            failures.push(read_failure(path, &e));
+            return;
+        }
+    };
+
+    let mut entries = directory
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let is_directory = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+            (entry.path(), is_directory)
+        })
+        .collect::<Vec<(PathBuf, bool)>>();
+
+    // Stable order makes the reports comparable between runs
+    entries.sort();
+
+    for (entry, is_directory) in entries {
+        if is_directory || (!entry.is_dir() && has_extension(&entry, extensions)) {
+            add_path(&entry, extensions, excludes, collected, failures);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect, has_extension, is_excluded};
+    use glob::Pattern;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn default_extensions_are_used() {
+        assert!(has_extension(Path::new("dir/script.tbasic"), &[]));
+        assert!(has_extension(Path::new("dir/script.TBASICC"), &[]));
+        assert!(!has_extension(Path::new("dir/script.inc"), &[]));
+        assert!(has_extension(
+            Path::new("dir/script.inc"),
+            &[".inc".to_string()]
+        ));
+    }
+
+    #[test]
+    fn excludes_match_path_or_file_name() {
+        let excludes = [
+            Pattern::new("*_old.tbasic").unwrap(),
+            Pattern::new("**/vendor/*").unwrap(),
+        ];
+
+        assert!(is_excluded(Path::new("scripts/test_old.tbasic"), &excludes));
+        assert!(is_excluded(
+            Path::new("scripts/vendor/lib.tbasic"),
+            &excludes
+        ));
+        assert!(!is_excluded(Path::new("scripts/test.tbasic"), &excludes));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_directories_are_not_followed() {
+        let root = std::env::temp_dir().join(format!("thinclippy-files-{}", std::process::id()));
+        let scripts = root.join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("main.tbasic"), "").unwrap();
+        std::os::unix::fs::symlink(&root, scripts.join("loop")).unwrap();
+
+        let collected = collect(&[root.to_string_lossy().to_string()], &[], &[]).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            collected.script_files,
+            vec![scripts.join("main.tbasic").to_string_lossy().to_string()]
+        );
+        assert!(collected.failures.is_empty());
+    }
+}
@@ -1,18 +1,69 @@
+use std::path::PathBuf;
 use std::process::exit;
 
 use structopt::StructOpt;
 use termcolor::Color;
 
+use config::Config;
+use rules::core::unused_suppression::UnusedSuppression;
+use rules::{ActiveRule, Rule};
+use thinbasic_script::Severity;
+
+mod config;
 mod console;
+mod files;
+mod parser;
+mod report;
 mod rules;
+mod symbols;
 mod thinbasic_script;
 mod tokenizer;
 
+/// Exit code when an issue at or above the fail level was found
+const EXIT_ISSUES_FOUND: i32 = 1;
+/// Exit code when a script or directory could not be read
+const EXIT_INPUT_ERROR: i32 = 2;
+/// Exit code when the configuration or the rule selection is invalid
+const EXIT_CONFIG_ERROR: i32 = 3;
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "thinClippy", about = "Tool for thinBasic code analysis.")]
 struct CommandLineParams {
-    /// Script file for analysis
-    script_file: String,
+    /// Script files, directories or glob patterns for analysis
+    #[structopt(raw(required_unless = r#""list_rules""#))]
+    script_files: Vec<String>,
+
+    /// Extension of scripts searched in directories, tbasic and tbasicc by default
+    #[structopt(short = "x", long = "extension", number_of_values = 1)]
+    extensions: Vec<String>,
+
+    /// Glob pattern of files and directories to skip, such as "*_old.tbasic"
+    #[structopt(long = "exclude", number_of_values = 1)]
+    excludes: Vec<String>,
+
+    /// Flag to list all the available rules instead of analysing a script
+    #[structopt(short = "l", long = "list-rules")]
+    list_rules: bool,
+
+    /// Minimal severity of issue which makes the analysis fail: error, warning, hint or info
+    #[structopt(long = "fail-level")]
+    fail_level: Option<Severity>,
+
+    /// Configuration file to use instead of thinclippy.toml found next to the script
+    #[structopt(short = "c", long = "config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Code or name of rule to enable, takes precedence over the configuration file
+    #[structopt(short = "e", long = "enable", number_of_values = 1)]
+    enable: Vec<String>,
+
+    /// Code or name of rule to disable, takes precedence over the configuration file
+    #[structopt(short = "d", long = "disable", number_of_values = 1)]
+    disable: Vec<String>,
+
+    /// Output format: text, json, sarif, short, checkstyle or junit
+    #[structopt(short = "f", long = "format", default_value = "text")]
+    format: report::Format,
 
     /// Flag to wait with report display till ENTER confirms it
     #[structopt(short = "w", long = "wait")]
@@ -22,71 +73,247 @@ struct CommandLineParams {
 fn main() {
     let command_line_params = CommandLineParams::from_args();
 
-    println!("{}", "-".repeat(80));
-    println!("{}", command_line_params.script_file);
-    println!("{}", "-".repeat(80));
+    if command_line_params.list_rules {
+        print_rules();
+        end_program(0, command_line_params)
+    }
 
-    let mut code = match thinbasic_script::Code::new(&command_line_params.script_file) {
-        Ok(c) => c,
+    let collected = match files::collect(
+        &command_line_params.script_files,
+        &command_line_params.extensions,
+        &command_line_params.excludes,
+    ) {
+        Ok(f) => f,
         Err(e) => {
-            console::print_color("input error: ", Color::Red);
-            println!("{}", e);
+            eprintln!("input error: {}", e);
 
-            end_program(1, command_line_params)
+            end_program(EXIT_INPUT_ERROR, command_line_params)
         }
     };
 
-    let issues = get_issues(&mut code);
+    for failure in &collected.failures {
+        eprintln!("input error: {}", failure.message);
+    }
+
+    let mut reports: Vec<report::ScriptReport> = vec![];
+    let mut failures = collected.failures;
+    let mut issues_found = false;
+    let mut config_failed = false;
+
+    for script_file in collected.script_files {
+        let mut code = match thinbasic_script::Code::new(&script_file) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("input error: {}", e);
+
+                failures.push(report::ScriptFailure {
+                    script_file,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        // Bad configuration of one directory should not hide reports of the others
+        let configured = load_config(&command_line_params, &script_file).and_then(|config| {
+            configure_rules(&config, &command_line_params).map(|rules| (config, rules))
+        });
+
+        let (config, active_rules) = match configured {
+            Ok(c) => c,
+            Err(e) => {
+                let message = format!("{}: {}", script_file, e);
+                eprintln!("config error: {}", message);
+
+                config_failed = true;
+                failures.push(report::ScriptFailure {
+                    script_file,
+                    message,
+                });
+                continue;
+            }
+        };
+
+        let issues = get_issues(&mut code, &active_rules);
 
-    print_issues(&issues, &mut code);
+        let fail_level = command_line_params
+            .fail_level
+            .or(config.fail_level)
+            .unwrap_or(Severity::Info);
 
-    end_program((!issues.is_empty()) as i32, command_line_params);
+        issues_found |= issues
+            .iter()
+            .any(|issue| !issue.suppressed && issue.severity >= fail_level);
+
+        reports.push(report::ScriptReport {
+            script_file,
+            source: code.get_file_content().clone(),
+            encoding: code.encoding,
+            issues,
+        });
+    }
+
+    report::print(command_line_params.format, &reports, &failures);
+
+    // Scripts which could not be analysed at all are worse than those with issues
+    let exit_code = if config_failed {
+        EXIT_CONFIG_ERROR
+    } else if !failures.is_empty() {
+        EXIT_INPUT_ERROR
+    } else if issues_found {
+        EXIT_ISSUES_FOUND
+    } else {
+        0
+    };
+
+    end_program(exit_code, command_line_params);
 }
 
-fn get_issues(mut code: &mut thinbasic_script::Code) -> Vec<thinbasic_script::IssueSummary> {
-    let mut issues: Vec<thinbasic_script::IssueSummary> = vec![];
+fn load_config(
+    command_line_params: &CommandLineParams,
+    script_file: &str,
+) -> Result<Config, String> {
+    let config_file = match command_line_params.config {
+        Some(ref path) => Some(path.clone()),
+        None => Config::find(script_file),
+    };
 
-    let mut compiled_issues = rules::compiled::section_definition(&mut code);
-    issues.append(&mut compiled_issues);
+    match config_file {
+        Some(path) => Config::load(&path),
+        None => Ok(Config::default()),
+    }
+}
 
-    let mut alias_issues = rules::core::alias::check(&mut code);
-    issues.append(&mut alias_issues);
+/// Picks the enabled rules and their severities; command line wins over the configuration file
+fn configure_rules(
+    config: &Config,
+    command_line_params: &CommandLineParams,
+) -> Result<Vec<ActiveRule>, String> {
+    let all_rules = rules::registry();
 
-    issues.sort_by(|a, b| a.line.cmp(&b.line));
+    config.validate(&all_rules)?;
 
-    issues
+    for requested in command_line_params
+        .enable
+        .iter()
+        .chain(command_line_params.disable.iter())
+    {
+        if !all_rules
+            .iter()
+            .any(|rule| rule.id() == requested || rule.name() == requested)
+        {
+            return Err(format!("Unknown rule '{}' on command line", requested));
+        }
+    }
+
+    let is_listed = |list: &[String], rule: &dyn Rule| {
+        list.iter()
+            .any(|item| item == rule.id() || item == rule.name())
+    };
+
+    let mut active_rules = vec![];
+
+    for mut rule in all_rules {
+        let rule_config = config.rule(rule.as_ref());
+
+        // Options of disabled rules are checked too, typos must not pass silently
+        if let Some(rule_config) = rule_config {
+            rule.configure(rule_config)
+                .map_err(|e| format!("{} ({}): {}", rule.id(), rule.name(), e))?;
+        }
+
+        let enabled = if is_listed(&command_line_params.disable, rule.as_ref()) {
+            false
+        } else if is_listed(&command_line_params.enable, rule.as_ref()) {
+            true
+        } else {
+            rule_config
+                .and_then(|c| c.enabled)
+                .unwrap_or_else(|| rule.enabled_by_default())
+        };
+
+        if !enabled {
+            continue;
+        }
+
+        let severity = rule_config
+            .and_then(|c| c.severity)
+            .unwrap_or_else(|| rule.default_severity());
+
+        active_rules.push(ActiveRule { rule, severity });
+    }
+
+    Ok(active_rules)
 }
 
-fn print_issues(issues: &[thinbasic_script::IssueSummary], code: &mut thinbasic_script::Code) {
-    for issue in issues {
-        let mut lines = code.get_file_content().unwrap().lines();
+fn get_issues(
+    code: &mut thinbasic_script::Code,
+    active_rules: &[ActiveRule],
+) -> Vec<thinbasic_script::IssueSummary> {
+    let mut issues: Vec<thinbasic_script::IssueSummary> = vec![];
 
-        print!("Line {:>5} - ", issue.line);
+    for active_rule in active_rules {
+        let rule = &active_rule.rule;
+        let mut rule_issues = rule.check(code);
 
-        console::print_color(lines.nth((issue.line - 1) as usize).unwrap(), Color::White);
-        println!();
+        for issue in &mut rule_issues {
+            issue.set_rule(rule.id(), rule.name(), active_rule.severity);
+        }
+
+        issues.append(&mut rule_issues);
+    }
 
-        print!("{}", " ".repeat((issue.pos + 12) as usize));
-        println!("^");
-        print!("{}", " ".repeat((13) as usize));
-        console::print_color(&issue.summary, Color::Red);
+    let tokens = code.get_tokens();
+    let mut suppressions = thinbasic_script::Suppressions::from_tokens(tokens);
 
-        println!("\n{}", "-".repeat(80));
+    for issue in &mut issues {
+        suppressions.apply(issue);
     }
-    println!();
 
-    print!("{}", "-".repeat(80));
-    print!("\nAnalysis finished: ");
+    let unused_suppression = active_rules
+        .iter()
+        .find(|active_rule| active_rule.rule.id() == UnusedSuppression.id());
 
-    if issues.is_empty() {
-        console::print_color("no issues found\n", Color::Green);
-    } else {
-        console::print_color(&format!("{}", issues.len()), Color::Red);
-        console::print_color(" issue(s) found\n", Color::Red);
+    if let Some(unused_suppression) = unused_suppression {
+        let all_rules = rules::registry();
+
+        let is_known = |rule_reference: &str| {
+            all_rules
+                .iter()
+                .any(|rule| is_rule_reference(rule.as_ref(), rule_reference))
+        };
+        let is_active = |rule_reference: &str| {
+            active_rules
+                .iter()
+                .any(|active_rule| is_rule_reference(active_rule.rule.as_ref(), rule_reference))
+        };
+
+        for mut issue in suppressions.unused_issues(&code.main_file_name, &is_known, &is_active) {
+            issue.set_rule(
+                unused_suppression.rule.id(),
+                unused_suppression.rule.name(),
+                unused_suppression.severity,
+            );
+            issues.push(issue);
+        }
     }
 
-    print!("{}", "-".repeat(80));
-    println!();
+    issues.sort_by_key(|issue| (issue.line, issue.pos));
+
+    issues
+}
+
+/// Suppression comments refer to rules by code or name, in any letter case
+fn is_rule_reference(rule: &dyn Rule, reference: &str) -> bool {
+    rule.id().eq_ignore_ascii_case(reference) || rule.name().eq_ignore_ascii_case(reference)
+}
+
+fn print_rules() {
+    for rule in rules::registry() {
+        console::print_color(rule.id(), Color::White);
+        println!(" {} ({})", rule.name(), rule.default_severity());
+        println!("       {}", rule.description());
+    }
 }
 
 fn end_program(exit_code: i32, command_line_params: CommandLineParams) -> ! {
@@ -96,3 +323,53 @@ fn end_program(exit_code: i32, command_line_params: CommandLineParams) -> ! {
 
     exit(exit_code)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{configure_rules, CommandLineParams};
+    use crate::config::Config;
+    use structopt::StructOpt;
+
+    fn active_rule_ids(config: &str, arguments: &[&str]) -> Result<Vec<&'static str>, String> {
+        let config: Config = toml::from_str(config).unwrap();
+        let command_line_params = CommandLineParams::from_iter(
+            ["thinclippy"]
+                .iter()
+                .chain(arguments.iter())
+                .chain(["script.tbasic"].iter()),
+        );
+
+        configure_rules(&config, &command_line_params)
+            .map(|rules| rules.iter().map(|active| active.rule.id()).collect())
+    }
+
+    #[test]
+    fn command_line_overrides_config() {
+        let config = r#"
+            [rules.TC0001]
+            enabled = true
+
+            [rules.line-length]
+            enabled = false
+            "#;
+
+        let ids = active_rule_ids(config, &[]).unwrap();
+        assert!(ids.contains(&"TC0001"));
+        assert!(!ids.contains(&"TC0003"));
+
+        let ids = active_rule_ids(config, &["--disable", "alias-syntax", "-e", "TC0003"]).unwrap();
+        assert!(!ids.contains(&"TC0001"));
+        assert!(ids.contains(&"TC0003"));
+
+        // Disabling wins when the rule is given both ways
+        let ids = active_rule_ids("", &["-e", "TC0002", "-d", "TC0002"]).unwrap();
+        assert!(!ids.contains(&"TC0002"));
+    }
+
+    #[test]
+    fn invalid_rule_references_are_rejected() {
+        assert!(active_rule_ids("", &["-d", "TC9999"]).is_err());
+        assert!(active_rule_ids("[rules.TC0001]\nenable = false\n", &[]).is_err());
+        assert!(active_rule_ids("[rules.TC0003]\nmax-lenght = 80\n", &[]).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::tokenizer::{Comparator, Directive, Keyword, NumberLiteral, Operator, Span};
+
+/// Whole script as the list of its top level statements
+#[derive(Debug, Clone, PartialEq)]
+pub struct Script {
+    pub statements: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementKind {
+    /// x = 1, LET x = 1 or compound x += 1, which carries its operator
+    Assignment {
+        target: Expression,
+        operator: Option<Operator>,
+        value: Expression,
+    },
+    /// PRINT "a", b or MyFunction(1), optionally preceded by CALL
+    Call {
+        target: Expression,
+        arguments: Vec<Expression>,
+    },
+    /// DIM, LOCAL, GLOBAL, STATIC or REDIM, as given by the keyword
+    Declaration {
+        keyword: Keyword,
+        variables: Vec<Variable>,
+    },
+    /// %NUMERIC_EQUATE = 1 or $STRING_EQUATE = "text"
+    Equate {
+        name: Name,
+        value: Expression,
+    },
+    /// BEGIN CONST ... END CONST
+    ConstBlock {
+        body: Vec<Statement>,
+    },
+    /// IF with all its ELSEIF branches, written on a single line or as a block
+    If {
+        branches: Vec<ConditionalBlock>,
+        else_body: Option<Vec<Statement>>,
+    },
+    For {
+        variable: Name,
+        from: Expression,
+        to: Expression,
+        step: Option<Expression>,
+        body: Vec<Statement>,
+    },
+    /// FOR EACH variable IN array
+    ForEach {
+        variable: Name,
+        collection: Expression,
+        body: Vec<Statement>,
+    },
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+    },
+    /// DO [WHILE|UNTIL condition] ... LOOP [WHILE|UNTIL condition]
+    Do {
+        condition: Option<LoopCondition>,
+        body: Vec<Statement>,
+        loop_condition: Option<LoopCondition>,
+    },
+    Select {
+        subject: Expression,
+        cases: Vec<Case>,
+        else_body: Option<Vec<Statement>>,
+    },
+    With {
+        object: Expression,
+        body: Vec<Statement>,
+    },
+    /// FUNCTION or SUB
+    Routine(Routine),
+    /// DECLARE FUNCTION or DECLARE SUB of external library
+    Declare(Declare),
+    /// TYPE ... END TYPE, members of nested UNION blocks are listed with the others,
+    /// as they are accessed the same way
+    Type {
+        name: Name,
+        members: Vec<Variable>,
+    },
+    /// ALIAS keyword AS name, parts are missing when malformed, as alias-syntax reports them
+    Alias {
+        keyword: Option<Name>,
+        alias: Option<Name>,
+    },
+    /// Directive such as #INCLUDE, with the rest of its line as written
+    Directive {
+        directive: Directive,
+        arguments: String,
+    },
+    /// #COMPILED ... #ENDCOMPILED, containing code of another language
+    CompiledSection,
+    /// EXIT with the construct it leaves, such as FOR
+    Exit(Option<Keyword>),
+    /// ITERATE with the loop it continues, such as FOR
+    Iterate(Option<Keyword>),
+    Return(Option<Expression>),
+    Goto(Name),
+    Gosub(Name),
+    /// Statement which could not be parsed, with the body of the block it opens
+    Error {
+        body: Vec<Statement>,
+    },
+}
+
+/// Condition with the statements it guards
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalBlock {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopCondition {
+    /// UNTIL instead of WHILE
+    pub until: bool,
+    pub condition: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub items: Vec<CaseItem>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseItem {
+    Value(Expression),
+    /// CASE 1 TO 5
+    Range(Expression, Expression),
+    /// CASE IS > 5
+    Comparison(BinaryOperator, Expression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Routine {
+    /// FUNCTION or SUB
+    pub keyword: Keyword,
+    pub name: Name,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<DataType>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declare {
+    /// FUNCTION or SUB
+    pub keyword: Keyword,
+    pub name: Name,
+    pub library: Option<String>,
+    pub alias: Option<String>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<DataType>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    /// BYVAL or BYREF, when specified
+    pub passing: Option<Keyword>,
+    pub optional: bool,
+    pub name: Name,
+    /// Written with () as array
+    pub array: bool,
+    pub data_type: Option<DataType>,
+    pub default: Option<Expression>,
+}
+
+/// Variable declared by DIM and alike, or member of TYPE
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    pub name: Name,
+    pub dimensions: Vec<Dimension>,
+    pub data_type: Option<DataType>,
+    /// AT address, placing the variable over existing memory
+    pub at: Option<Expression>,
+    pub value: Option<Expression>,
+}
+
+/// Bounds of array dimension, such as (10) or (1 TO 10)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dimension {
+    pub lower: Option<Expression>,
+    pub upper: Expression,
+}
+
+/// Type after AS, such as LONG, STRING * 10 or name of TYPE
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataType {
+    pub name: Name,
+    /// Length of fixed size STRING
+    pub length: Option<Expression>,
+    /// Followed by PTR, such as LONG PTR
+    pub pointer: bool,
+}
+
+/// Name as written in the script
+#[derive(Debug, Clone, PartialEq)]
+pub struct Name {
+    /// Name in uppercase, as thinBasic is not case sensitive
+    pub name: String,
+    /// Original spelling
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    Number(NumberLiteral),
+    /// String literal including its quotes
+    Text(String),
+    Equate(Name),
+    /// Variable, function or anything else referenced by name
+    Name(Name),
+    /// Function call or array element, which look the same
+    Call {
+        target: Box<Expression>,
+        arguments: Vec<Expression>,
+    },
+    /// object.member, inside WITH the object can be omitted
+    Member {
+        object: Option<Box<Expression>>,
+        member: Name,
+    },
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// Expression which could not be parsed
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Negate,
+    Plus,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Operator(Operator),
+    Comparator(Comparator),
+    /// = compares inside of expressions
+    Equal,
+}
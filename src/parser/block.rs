@@ -0,0 +1,407 @@
+use std::fmt;
+
+use super::ast::{Statement, StatementKind};
+use super::{Parser, SyntaxError};
+use crate::tokenizer::{Keyword, Span, TokenType};
+
+/// Keyword ending the body of block, such as NEXT or END IF
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockEnd {
+    Next,
+    Wend,
+    Loop,
+    Case,
+    Else,
+    ElseIf,
+    EndIf,
+    EndSelect,
+    EndFunction,
+    EndSub,
+    EndType,
+    EndUnion,
+    EndWith,
+    EndConst,
+}
+
+impl BlockEnd {
+    /// Ends the whole block, rather than a part of it such as ELSE does
+    fn is_final(self) -> bool {
+        !matches!(self, BlockEnd::Case | BlockEnd::Else | BlockEnd::ElseIf)
+    }
+
+    /// Statement which opens the block ended by this keyword
+    fn block_name(self) -> &'static str {
+        match self {
+            BlockEnd::Next => "FOR",
+            BlockEnd::Wend => "WHILE",
+            BlockEnd::Loop => "DO",
+            BlockEnd::Else | BlockEnd::ElseIf | BlockEnd::EndIf => "IF",
+            BlockEnd::Case | BlockEnd::EndSelect => "SELECT CASE",
+            BlockEnd::EndFunction => "FUNCTION",
+            BlockEnd::EndSub => "SUB",
+            BlockEnd::EndType => "TYPE",
+            BlockEnd::EndUnion => "UNION",
+            BlockEnd::EndWith => "WITH",
+            BlockEnd::EndConst => "BEGIN CONST",
+        }
+    }
+}
+
+/// Block being parsed, with the keyword which opened it
+pub struct OpenBlock {
+    ends: &'static [BlockEnd],
+    opener: Span,
+}
+
+impl fmt::Display for BlockEnd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            BlockEnd::Next => "NEXT",
+            BlockEnd::Wend => "WEND",
+            BlockEnd::Loop => "LOOP",
+            BlockEnd::Case => "CASE",
+            BlockEnd::Else => "ELSE",
+            BlockEnd::ElseIf => "ELSEIF",
+            BlockEnd::EndIf => "END IF",
+            BlockEnd::EndSelect => "END SELECT",
+            BlockEnd::EndFunction => "END FUNCTION",
+            BlockEnd::EndSub => "END SUB",
+            BlockEnd::EndType => "END TYPE",
+            BlockEnd::EndUnion => "END UNION",
+            BlockEnd::EndWith => "END WITH",
+            BlockEnd::EndConst => "END CONST",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+// Ends accepted by the body of each block, the final one goes last
+pub const IF_ENDS: [BlockEnd; 3] = [BlockEnd::ElseIf, BlockEnd::Else, BlockEnd::EndIf];
+pub const ELSE_ENDS: [BlockEnd; 1] = [BlockEnd::EndIf];
+pub const FOR_ENDS: [BlockEnd; 1] = [BlockEnd::Next];
+pub const WHILE_ENDS: [BlockEnd; 1] = [BlockEnd::Wend];
+pub const DO_ENDS: [BlockEnd; 1] = [BlockEnd::Loop];
+pub const SELECT_ENDS: [BlockEnd; 2] = [BlockEnd::Case, BlockEnd::EndSelect];
+pub const FUNCTION_ENDS: [BlockEnd; 1] = [BlockEnd::EndFunction];
+pub const SUB_ENDS: [BlockEnd; 1] = [BlockEnd::EndSub];
+pub const TYPE_ENDS: [BlockEnd; 1] = [BlockEnd::EndType];
+pub const UNION_ENDS: [BlockEnd; 1] = [BlockEnd::EndUnion];
+pub const WITH_ENDS: [BlockEnd; 1] = [BlockEnd::EndWith];
+pub const CONST_ENDS: [BlockEnd; 1] = [BlockEnd::EndConst];
+
+impl Parser {
+    /// Body of the block opened by the given keyword, the end is left for the caller
+    pub(super) fn parse_block(
+        &mut self,
+        ends: &'static [BlockEnd],
+        opener: Span,
+    ) -> Vec<Statement> {
+        self.open_blocks.push(OpenBlock { ends, opener });
+        let statements = self.parse_statements();
+        self.open_blocks.pop();
+
+        statements
+    }
+
+    /// Statements up to the end of file or to the end of any open block
+    ///
+    /// Ends which do not belong to any open block are reported and skipped.
+    pub(super) fn parse_statements(&mut self) -> Vec<Statement> {
+        let mut statements = vec![];
+
+        loop {
+            self.skip_separators();
+
+            if self.peek().is_none() {
+                break;
+            }
+
+            if let Some(end) = self.peek_block_end() {
+                if self.open_blocks.iter().any(|open| open.ends.contains(&end)) {
+                    break;
+                }
+
+                let start = self.start();
+                self.bump_block_end(end);
+                let span = self.span_from(start);
+
+                // Most likely the innermost block was not closed
+                let error = match self.open_blocks.last() {
+                    Some(open) => {
+                        let expected = open.ends[open.ends.len() - 1];
+
+                        SyntaxError {
+                            span,
+                            message: format!(
+                                "{} does not match {}, expected {}",
+                                end,
+                                expected.block_name(),
+                                expected
+                            ),
+                            related: Some((
+                                open.opener,
+                                format!("{} starts here", expected.block_name()),
+                            )),
+                        }
+                    }
+                    None => SyntaxError {
+                        span,
+                        message: format!("{} does not close any block", end),
+                        related: None,
+                    },
+                };
+
+                self.errors.push(error);
+                self.skip_statement();
+
+                statements.push(Statement {
+                    kind: StatementKind::Error { body: vec![] },
+                    span: self.span_from(start),
+                });
+                continue;
+            }
+
+            statements.push(self.parse_statement_with_recovery());
+        }
+
+        statements
+    }
+
+    /// Reports missing end together with the start of the block, which is closed anyway
+    pub(super) fn expect_block_end(&mut self, end: BlockEnd, opener: Span) {
+        if self.peek_block_end() == Some(end) {
+            self.bump_block_end(end);
+        } else {
+            let mut error = self.error(&end.to_string());
+            error.related = Some((opener, format!("{} starts here", end.block_name())));
+
+            self.errors.push(error);
+        }
+    }
+
+    pub(super) fn peek_block_end(&self) -> Option<BlockEnd> {
+        let end = match self.peek_keyword()? {
+            Keyword::Next => BlockEnd::Next,
+            Keyword::Wend => BlockEnd::Wend,
+            Keyword::Loop => BlockEnd::Loop,
+            Keyword::Case => BlockEnd::Case,
+            Keyword::Else => BlockEnd::Else,
+            Keyword::ElseIf => BlockEnd::ElseIf,
+            Keyword::End => match self.peek_keyword_nth(1)? {
+                Keyword::If => BlockEnd::EndIf,
+                Keyword::Select => BlockEnd::EndSelect,
+                Keyword::Function => BlockEnd::EndFunction,
+                Keyword::Sub => BlockEnd::EndSub,
+                Keyword::Type => BlockEnd::EndType,
+                Keyword::Union => BlockEnd::EndUnion,
+                Keyword::With => BlockEnd::EndWith,
+                Keyword::Const => BlockEnd::EndConst,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(end)
+    }
+
+    fn bump_block_end(&mut self, end: BlockEnd) {
+        self.bump();
+
+        // END is followed by keyword of the block
+        if end.is_final() && !matches!(end, BlockEnd::Next | BlockEnd::Wend | BlockEnd::Loop) {
+            self.bump();
+        }
+    }
+
+    /// Statement, which is replaced by error node when it cannot be parsed
+    fn parse_statement_with_recovery(&mut self) -> Statement {
+        let start = self.start();
+        let opener = self
+            .peek()
+            .map_or_else(|| self.span_from(start), |token| token.span);
+        let opened_block = self.peek_opened_block();
+
+        match self.parse_statement() {
+            Ok(statement) => {
+                // Block missing its end is followed by the end of the outer block
+                if !self.at_statement_end() && self.peek_block_end().is_none() {
+                    let error = self.error("end of statement");
+                    self.errors.push(error);
+                    self.skip_statement();
+                }
+
+                statement
+            }
+            Err(error) => {
+                self.errors.push(error);
+                self.skip_statement();
+
+                // Body of the block is still parsed, so its end is not reported as stray
+                let body = match opened_block {
+                    Some(ends) => self.parse_error_body(ends, opener),
+                    None => vec![],
+                };
+
+                Statement {
+                    kind: StatementKind::Error { body },
+                    span: self.span_from(start),
+                }
+            }
+        }
+    }
+
+    /// Ends of the block opened by the statement starting at the current token
+    fn peek_opened_block(&self) -> Option<&'static [BlockEnd]> {
+        let is_callback =
+            matches!(self.peek_type(), Some(TokenType::Identifier(name)) if name == "CALLBACK");
+        let keyword = if is_callback {
+            self.peek_keyword_nth(1)?
+        } else {
+            self.peek_keyword()?
+        };
+
+        match keyword {
+            Keyword::If if self.is_block_if() => Some(&IF_ENDS),
+            Keyword::For => Some(&FOR_ENDS),
+            Keyword::While => Some(&WHILE_ENDS),
+            Keyword::Do => Some(&DO_ENDS),
+            Keyword::Select => Some(&SELECT_ENDS),
+            Keyword::Function
+                if self.peek_nth(1).map(|token| &token.token_type)
+                    != Some(&TokenType::EqualSign) =>
+            {
+                Some(&FUNCTION_ENDS)
+            }
+            Keyword::Sub => Some(&SUB_ENDS),
+            Keyword::Type => Some(&TYPE_ENDS),
+            Keyword::Union => Some(&UNION_ENDS),
+            Keyword::With => Some(&WITH_ENDS),
+            Keyword::Begin => Some(&CONST_ENDS),
+            _ => None,
+        }
+    }
+
+    /// IF with THEN as the last token of its line
+    fn is_block_if(&self) -> bool {
+        let line_end = self.tokens[self.index..]
+            .iter()
+            .position(|token| token.token.token_type == TokenType::EndOfLine)
+            .map_or(self.tokens.len(), |position| self.index + position);
+
+        line_end > self.index
+            && self.tokens[line_end - 1].token.token_type == TokenType::Keyword(Keyword::Then)
+    }
+
+    /// Body of block whose opening statement could not be parsed, with all its parts
+    fn parse_error_body(&mut self, ends: &'static [BlockEnd], opener: Span) -> Vec<Statement> {
+        let mut body = vec![];
+
+        loop {
+            body.extend(self.parse_block(ends, opener));
+
+            match self.peek_block_end() {
+                Some(end) if ends.contains(&end) => {
+                    self.bump_block_end(end);
+
+                    // Rest of the line, such as condition after ELSEIF or LOOP
+                    self.skip_statement();
+
+                    if end.is_final() {
+                        return body;
+                    }
+                }
+                _ => {
+                    if let Some(&end) = ends.last() {
+                        self.expect_block_end(end, opener);
+                    }
+
+                    return body;
+                }
+            }
+        }
+    }
+
+    /// Skips the rest of the statement after syntax error
+    pub(super) fn skip_statement(&mut self) {
+        while !self.at_statement_end() {
+            self.bump();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ast::StatementKind;
+    use super::super::parse;
+    use crate::tokenizer::get_tokens;
+
+    #[test]
+    fn recovery_keeps_parsing() {
+        let (script, errors) = parse(get_tokens(
+            "Function F()\n\
+               x = (1 +\n\
+               For i = 1 Tu 10\n\
+                 y = 2 3\n\
+               Next\n\
+             End Function\n\
+             Wend\n\
+             z = 1\n",
+        ));
+
+        let messages: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Expected expression, found end of line",
+                "Expected TO, found 'Tu'",
+                "Expected end of statement, found '3'",
+                "WEND does not close any block",
+            ]
+        );
+
+        assert_eq!(script.statements.len(), 3);
+
+        match &script.statements[0].kind {
+            StatementKind::Routine(routine) => {
+                assert_eq!(routine.body.len(), 2);
+                assert!(
+                    matches!(&routine.body[1].kind, StatementKind::Error { body } if body.len() == 1)
+                );
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+
+        assert!(matches!(
+            &script.statements[2].kind,
+            StatementKind::Assignment { .. }
+        ));
+    }
+
+    #[test]
+    fn missing_end_closes_block() {
+        let (script, errors) = parse(get_tokens("While a\n  If b Then\n    c\nWend\n"));
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Expected END IF, found 'Wend'");
+        assert_eq!(script.statements.len(), 1);
+
+        let (opener, message) = errors[0].related.as_ref().unwrap();
+        assert_eq!((opener.start.line, opener.start.column), (2, 3));
+        assert_eq!(message, "IF starts here");
+    }
+
+    #[test]
+    fn stray_end_points_at_open_block() {
+        let (_, errors) = parse(get_tokens("Sub S\n  While a\n  Next\n  Wend\nEnd Sub\n"));
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "NEXT does not match WHILE, expected WEND"
+        );
+        assert_eq!(errors[0].span.start.line, 3);
+        assert_eq!(errors[0].related.as_ref().unwrap().0.start.line, 2);
+    }
+}
@@ -0,0 +1,304 @@
+use super::ast::{BinaryOperator, Expression, ExpressionKind, UnaryOperator};
+use super::{Parser, SyntaxError};
+use crate::tokenizer::{Keyword, Operator, Position, Span, TokenType};
+
+/// Operand of NOT takes comparisons, so NOT a = b negates the comparison
+const NOT_POWER: u8 = 6;
+
+/// Operand of unary minus takes only ^, so -2 ^ 2 is -4
+const UNARY_POWER: u8 = 14;
+
+/// Keywords of data types, which are also names of conversion functions
+const TYPE_KEYWORDS: [Keyword; 15] = [
+    Keyword::Boolean,
+    Keyword::Byte,
+    Keyword::Currency,
+    Keyword::Double,
+    Keyword::Dword,
+    Keyword::Ext,
+    Keyword::Extended,
+    Keyword::Integer,
+    Keyword::Long,
+    Keyword::Number,
+    Keyword::Quad,
+    Keyword::Single,
+    Keyword::String,
+    Keyword::Variant,
+    Keyword::Word,
+];
+
+/// Binding power of binary operator, higher binds tighter
+fn binding_power(operator: BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::Operator(operator) => match operator {
+            Operator::Imp => 1,
+            Operator::Eqv => 2,
+            Operator::Xor => 3,
+            Operator::Or => 4,
+            Operator::And => 5,
+            Operator::Not => NOT_POWER,
+            Operator::Shl | Operator::Shr => 8,
+            Operator::Concatenate => 9,
+            Operator::Add | Operator::Subtract => 10,
+            Operator::Mod => 11,
+            Operator::IntegerDivide => 12,
+            Operator::Multiply | Operator::Divide => 13,
+            Operator::Power => 15,
+        },
+        BinaryOperator::Comparator(_) | BinaryOperator::Equal => 7,
+    }
+}
+
+impl Parser {
+    pub(super) fn parse_expression(&mut self) -> Result<Expression, SyntaxError> {
+        self.parse_binary(0)
+    }
+
+    /// Parses operators binding tighter than the given power, all of them being left associative
+    fn parse_binary(&mut self, min_power: u8) -> Result<Expression, SyntaxError> {
+        let start = self.start();
+        let mut left = self.parse_unary()?;
+
+        while let Some(operator) = self.peek_binary_operator() {
+            let power = binding_power(operator);
+
+            if power <= min_power {
+                break;
+            }
+
+            self.bump();
+            let right = self.parse_binary(power)?;
+
+            left = self.expression_from(
+                start,
+                ExpressionKind::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            );
+        }
+
+        Ok(left)
+    }
+
+    fn peek_binary_operator(&self) -> Option<BinaryOperator> {
+        match self.peek_type()? {
+            TokenType::Operator(Operator::Not) => None,
+            TokenType::Operator(operator) => Some(BinaryOperator::Operator(*operator)),
+            TokenType::Comparator(comparator) => Some(BinaryOperator::Comparator(*comparator)),
+            TokenType::EqualSign => Some(BinaryOperator::Equal),
+            _ => None,
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, SyntaxError> {
+        let start = self.start();
+
+        let (operator, power) = match self.peek_type() {
+            Some(TokenType::Operator(Operator::Subtract)) => (UnaryOperator::Negate, UNARY_POWER),
+            Some(TokenType::Operator(Operator::Add)) => (UnaryOperator::Plus, UNARY_POWER),
+            Some(TokenType::Operator(Operator::Not)) => (UnaryOperator::Not, NOT_POWER),
+            _ => return self.parse_postfix_expression(),
+        };
+
+        self.bump();
+        let operand = self.parse_binary(power)?;
+
+        Ok(self.expression_from(
+            start,
+            ExpressionKind::Unary {
+                operator,
+                operand: Box::new(operand),
+            },
+        ))
+    }
+
+    /// Operand with its calls and members, which binds tighter than any operator
+    pub(super) fn parse_postfix_expression(&mut self) -> Result<Expression, SyntaxError> {
+        let primary = self.parse_primary()?;
+        self.parse_postfix(primary)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, SyntaxError> {
+        let start = self.start();
+
+        let kind = match self.peek_type() {
+            Some(TokenType::Number(number)) => {
+                let number = number.clone();
+                self.bump();
+                ExpressionKind::Number(number)
+            }
+            Some(TokenType::Text(text)) => {
+                let text = text.clone();
+                self.bump();
+                ExpressionKind::Text(text)
+            }
+            Some(TokenType::Equate(_)) => ExpressionKind::Equate(self.bump_name()),
+            Some(TokenType::Identifier(_)) => ExpressionKind::Name(self.bump_name()),
+            Some(TokenType::Keyword(keyword)) if TYPE_KEYWORDS.contains(keyword) => {
+                ExpressionKind::Name(self.bump_name())
+            }
+            Some(TokenType::Paren('(')) => {
+                self.bump();
+                let inner = self.parse_expression()?;
+                self.expect(&TokenType::Paren(')'), "')'")?;
+
+                // Parentheses are kept in the span only
+                inner.kind
+            }
+            Some(TokenType::Dot) => {
+                self.bump();
+                ExpressionKind::Member {
+                    object: None,
+                    member: self.expect_word("member name")?,
+                }
+            }
+            _ => return Err(self.error("expression")),
+        };
+
+        Ok(self.expression_from(start, kind))
+    }
+
+    pub(super) fn parse_postfix(&mut self, primary: Expression) -> Result<Expression, SyntaxError> {
+        let start = primary.span.start;
+        let mut expression = primary;
+
+        loop {
+            let kind = match self.peek_type() {
+                Some(TokenType::Paren('(')) => {
+                    self.bump();
+
+                    ExpressionKind::Call {
+                        target: Box::new(expression),
+                        arguments: self.parse_arguments()?,
+                    }
+                }
+                Some(TokenType::Dot) => {
+                    self.bump();
+
+                    ExpressionKind::Member {
+                        object: Some(Box::new(expression)),
+                        member: self.expect_word("member name")?,
+                    }
+                }
+                _ => return Ok(expression),
+            };
+
+            expression = self.expression_from(start, kind);
+        }
+    }
+
+    /// Comma separated expressions after opening parenthesis, up to the closing one
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, SyntaxError> {
+        let mut arguments = vec![];
+
+        if self.eat(&TokenType::Paren(')')) {
+            return Ok(arguments);
+        }
+
+        loop {
+            arguments.push(self.parse_expression()?);
+
+            if !self.eat(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.expect(&TokenType::Paren(')'), "')'")?;
+
+        Ok(arguments)
+    }
+
+    fn expression_from(&self, start: Position, kind: ExpressionKind) -> Expression {
+        Expression {
+            kind,
+            span: Span {
+                start,
+                end: self.previous_end.max(start),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ast::{BinaryOperator, Expression, ExpressionKind, StatementKind};
+    use super::super::parse;
+    use crate::tokenizer::{get_tokens, NumberValue, Operator};
+
+    fn parse_value(code: &str) -> Expression {
+        let (mut script, errors) = parse(get_tokens(&format!("x = {}", code)));
+        assert_eq!(errors, vec![]);
+
+        match script.statements.remove(0).kind {
+            StatementKind::Assignment { value, .. } => value,
+            other => panic!("Unexpected {:?}", other),
+        }
+    }
+
+    /// Writes the expression back with parentheses around each operation
+    fn show(expression: &Expression) -> String {
+        match &expression.kind {
+            ExpressionKind::Number(number) => match &number.value {
+                Ok(NumberValue::Integer(value)) => value.to_string(),
+                other => format!("{:?}", other),
+            },
+            ExpressionKind::Text(text) => text.clone(),
+            ExpressionKind::Equate(name) | ExpressionKind::Name(name) => name.name.clone(),
+            ExpressionKind::Call { target, arguments } => format!(
+                "{}({})",
+                show(target),
+                arguments
+                    .iter()
+                    .map(show)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            ExpressionKind::Member { object, member } => match object {
+                Some(object) => format!("{}.{}", show(object), member.name),
+                None => format!(".{}", member.name),
+            },
+            ExpressionKind::Unary { operator, operand } => {
+                format!("({:?} {})", operator, show(operand))
+            }
+            ExpressionKind::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let operator = match operator {
+                    BinaryOperator::Operator(Operator::Add) => "+".to_string(),
+                    BinaryOperator::Operator(Operator::Multiply) => "*".to_string(),
+                    BinaryOperator::Operator(Operator::Power) => "^".to_string(),
+                    BinaryOperator::Equal => "=".to_string(),
+                    other => format!("{:?}", other),
+                };
+
+                format!("({} {} {})", show(left), operator, show(right))
+            }
+            ExpressionKind::Error => "?".to_string(),
+        }
+    }
+
+    #[test]
+    fn operator_precedence() {
+        assert_eq!(show(&parse_value("1 + 2 * 3")), "(1 + (2 * 3))");
+        assert_eq!(show(&parse_value("(1 + 2) * 3")), "((1 + 2) * 3)");
+        assert_eq!(show(&parse_value("1 + 2 + 3")), "((1 + 2) + 3)");
+        assert_eq!(show(&parse_value("-2 ^ 2")), "(Negate (2 ^ 2))");
+        assert_eq!(
+            show(&parse_value("a = 1 Or Not b And c")),
+            "((A = 1) Operator(Or) ((Not B) Operator(And) C))"
+        );
+        assert_eq!(show(&parse_value("a.b(1).c + %EQ")), "(A.B(1).C + %EQ)");
+    }
+
+    #[test]
+    fn spans_cover_parentheses() {
+        let value = parse_value("(1 + 2) * 3");
+
+        assert_eq!(value.span.start.column, 5);
+        assert_eq!(value.span.end.column, 16);
+    }
+}
@@ -0,0 +1,378 @@
+// The implementation is split across multiple files
+pub mod ast;
+mod block;
+mod expression;
+mod statement;
+
+use self::ast::{Name, Script};
+use self::block::OpenBlock;
+use crate::tokenizer::{Keyword, Position, Span, SyntaxToken, SyntaxTokens, TokenInfo, TokenType};
+
+/// Code which does not follow the thinBasic grammar
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub span: Span,
+    pub message: String,
+
+    /// Another place the error concerns, such as start of the block which is not closed
+    pub related: Option<(Span, String)>,
+}
+
+/// Builds the syntax tree of the script
+///
+/// Statements which cannot be parsed are kept in the tree as error nodes, so the rest
+/// of the script is still analysed.
+pub fn parse(tokens: Vec<TokenInfo>) -> (Script, Vec<SyntaxError>) {
+    let mut parser = Parser::new(SyntaxTokens::new(tokens));
+    let statements = parser.parse_statements();
+
+    (Script { statements }, parser.errors)
+}
+
+/// Recursive descent parser over significant tokens, trivia is skipped as attached to them
+struct Parser {
+    tokens: Vec<SyntaxToken>,
+    index: usize,
+
+    /// End of the last consumed token
+    previous_end: Position,
+
+    /// Names introduced by ALIAS, standing for keywords
+    aliases: Vec<(String, Keyword)>,
+
+    /// Depth of single line IFs, where ELSE ends a statement
+    single_line_if: usize,
+
+    /// Blocks being parsed, the innermost last
+    open_blocks: Vec<OpenBlock>,
+
+    errors: Vec<SyntaxError>,
+}
+
+impl Parser {
+    fn new(syntax_tokens: SyntaxTokens) -> Parser {
+        Parser {
+            tokens: syntax_tokens.tokens,
+            index: 0,
+            previous_end: Position {
+                offset: 0,
+                line: 1,
+                column: 1,
+            },
+            aliases: vec![],
+            single_line_if: 0,
+            open_blocks: vec![],
+            errors: vec![],
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenInfo> {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&TokenInfo> {
+        self.tokens.get(self.index + n).map(|token| &token.token)
+    }
+
+    fn peek_type(&self) -> Option<&TokenType> {
+        self.peek().map(|token| &token.token_type)
+    }
+
+    /// Keyword of the token, names introduced by ALIAS included
+    fn peek_keyword_nth(&self, n: usize) -> Option<Keyword> {
+        match &self.peek_nth(n)?.token_type {
+            TokenType::Keyword(keyword) => Some(*keyword),
+            TokenType::Identifier(name) => self
+                .aliases
+                .iter()
+                .find(|(alias, _)| alias == name)
+                .map(|&(_, keyword)| keyword),
+            _ => None,
+        }
+    }
+
+    fn peek_keyword(&self) -> Option<Keyword> {
+        self.peek_keyword_nth(0)
+    }
+
+    /// Consumes keyword which opens block, returning its place
+    fn bump_opener(&mut self) -> Span {
+        let start = self.start();
+        self.bump();
+
+        self.span_from(start)
+    }
+
+    fn bump(&mut self) -> Option<TokenInfo> {
+        let token = self.peek()?.clone();
+
+        self.index += 1;
+        self.previous_end = token.span.end;
+
+        Some(token)
+    }
+
+    fn eat(&mut self, token_type: &TokenType) -> bool {
+        if self.peek_type() == Some(token_type) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: Keyword) -> bool {
+        if self.peek_keyword() == Some(keyword) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token_type: &TokenType, expected: &str) -> Result<(), SyntaxError> {
+        if self.eat(token_type) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: Keyword, expected: &str) -> Result<(), SyntaxError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    /// Name of variable, routine or anything else which is not a keyword
+    fn expect_name(&mut self, expected: &str) -> Result<Name, SyntaxError> {
+        match self.peek_type() {
+            Some(TokenType::Identifier(_)) => Ok(self.bump_name()),
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// Identifier or keyword, such as type name or member after dot
+    fn expect_word(&mut self, expected: &str) -> Result<Name, SyntaxError> {
+        match self.peek_type() {
+            Some(TokenType::Identifier(_)) | Some(TokenType::Keyword(_)) => Ok(self.bump_name()),
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn bump_name(&mut self) -> Name {
+        let start = self.start();
+        let text = self.bump().map(|token| token.text).unwrap_or_default();
+
+        Name {
+            name: text.to_uppercase(),
+            text,
+            span: self.span_from(start),
+        }
+    }
+
+    fn at_line_end(&self) -> bool {
+        matches!(self.peek_type(), None | Some(TokenType::EndOfLine))
+    }
+
+    fn at_statement_end(&self) -> bool {
+        self.at_line_end()
+            || self.peek_type() == Some(&TokenType::Colon)
+            || self.single_line_if > 0 && self.peek_keyword() == Some(Keyword::Else)
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(
+            self.peek_type(),
+            Some(TokenType::EndOfLine) | Some(TokenType::Colon)
+        ) {
+            self.bump();
+        }
+    }
+
+    /// Where the next token starts, or where the last one ended at the end of file
+    fn start(&self) -> Position {
+        self.peek()
+            .map_or(self.previous_end, |token| token.span.start)
+    }
+
+    fn span_from(&self, start: Position) -> Span {
+        Span {
+            start,
+            end: self.previous_end.max(start),
+        }
+    }
+
+    fn error(&self, expected: &str) -> SyntaxError {
+        let (span, found) = match self.peek() {
+            Some(token) if token.token_type == TokenType::EndOfLine => {
+                (token.span, "end of line".to_string())
+            }
+            Some(token) => (token.span, format!("'{}'", token.text)),
+            None => (
+                Span {
+                    start: self.previous_end,
+                    end: self.previous_end,
+                },
+                "end of file".to_string(),
+            ),
+        };
+
+        SyntaxError {
+            span,
+            message: format!("Expected {}, found {}", expected, found),
+            related: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ast::{ExpressionKind, StatementKind};
+    use super::parse;
+    use crate::tokenizer::{get_tokens, Keyword};
+
+    fn parse_code(code: &str) -> Vec<StatementKind> {
+        let (script, errors) = parse(get_tokens(code));
+        assert_eq!(errors, vec![]);
+
+        script
+            .statements
+            .into_iter()
+            .map(|statement| statement.kind)
+            .collect()
+    }
+
+    #[test]
+    fn parse_simple_statements() {
+        let statements = parse_code(
+            "Uses \"Console\"\r\n\
+             Dim a, b(10) As Long = 1 ' note\r\n\
+             a = b(2) + 1 : a += 2\r\n\
+             PrintL \"Sum: \" + Str$(a), _\r\n  a\r\n",
+        );
+
+        assert_eq!(statements.len(), 5);
+
+        match &statements[1] {
+            StatementKind::Declaration { keyword, variables } => {
+                assert_eq!(*keyword, Keyword::Dim);
+                assert_eq!(variables.len(), 2);
+                assert_eq!(variables[1].name.name, "B");
+                assert_eq!(variables[1].data_type.as_ref().unwrap().name.name, "LONG");
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+
+        assert!(matches!(
+            &statements[3],
+            StatementKind::Assignment {
+                operator: Some(_),
+                ..
+            }
+        ));
+
+        match &statements[4] {
+            StatementKind::Call { target, arguments } => {
+                assert!(
+                    matches!(&target.kind, ExpressionKind::Name(name) if name.name == "PRINTL")
+                );
+                assert_eq!(arguments.len(), 2);
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_blocks() {
+        let statements = parse_code(
+            "Function Sum(ByVal a As Long, Optional b As Long = 1) As Long\n\
+               Local i As Long\n\
+               For i = 1 To 10 Step 2\n\
+                 If i > 5 Then Exit For Else a += i\n\
+               Next\n\
+               For Each i In items\n\
+                 a += i\n\
+               Next i\n\
+               Select Case a\n\
+                 Case 1, 2 To 4, Is > 10\n\
+                   Function = 1\n\
+                 Case Else\n\
+                   Do While a > 0 : a -= 1 : Loop\n\
+               End Select\n\
+             End Function\n\
+             Type Point\n\
+               x As Long\n\
+               Union\n\
+                 y As Long\n\
+                 p As Byte Ptr\n\
+               End Union\n\
+             End Type\n\
+             Declare Function Beep Lib \"kernel32\" Alias \"Beep\" (ByVal f As Dword) As Long\n\
+             #Include \"lib.tbasic\"\n\
+             #Compiled Language = FreeBasic\n\
+               ? 'anything goes\n\
+             #EndCompiled\n",
+        );
+
+        assert_eq!(statements.len(), 5);
+
+        match &statements[0] {
+            StatementKind::Routine(routine) => {
+                assert_eq!(routine.name.text, "Sum");
+                assert_eq!(routine.parameters.len(), 2);
+                assert!(routine.parameters[1].optional);
+                assert_eq!(routine.body.len(), 4);
+                assert!(matches!(
+                    &routine.body[2].kind,
+                    StatementKind::ForEach { variable, .. } if variable.name == "I"
+                ));
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+
+        match &statements[1] {
+            StatementKind::Type { members, .. } => {
+                assert_eq!(members.len(), 3);
+                assert!(members[2].data_type.as_ref().unwrap().pointer);
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert!(
+            matches!(&statements[2], StatementKind::Declare(declare) if declare.library.is_some())
+        );
+        assert!(matches!(
+            &statements[3],
+            StatementKind::Directive { arguments, .. } if arguments == "\"lib.tbasic\""
+        ));
+        assert_eq!(statements[4], StatementKind::CompiledSection);
+    }
+
+    #[test]
+    fn alias_stands_for_keyword() {
+        let statements = parse_code("Alias Dim As Var\nVar x As Long\n");
+
+        assert!(matches!(
+            &statements[1],
+            StatementKind::Declaration {
+                keyword: Keyword::Dim,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn syntax_error_is_reported() {
+        let (_, errors) = parse(get_tokens("If a Then\n  Next\n"));
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "NEXT does not match IF, expected END IF");
+        assert_eq!(errors[0].span.start.line, 2);
+        assert_eq!(errors[1].message, "Expected END IF, found end of file");
+
+        let (_, errors) = parse(get_tokens("a = (1 + \n"));
+        assert_eq!(errors[0].message, "Expected expression, found end of line");
+    }
+}
@@ -0,0 +1,884 @@
+use super::ast::{
+    BinaryOperator, Case, CaseItem, ConditionalBlock, DataType, Declare, Dimension, Expression,
+    ExpressionKind, LoopCondition, Parameter, Routine, Statement, StatementKind, Variable,
+};
+use super::block::{
+    BlockEnd, CONST_ENDS, DO_ENDS, ELSE_ENDS, FOR_ENDS, FUNCTION_ENDS, IF_ENDS, SELECT_ENDS,
+    SUB_ENDS, WHILE_ENDS, WITH_ENDS,
+};
+use super::{Parser, SyntaxError};
+use crate::tokenizer::{Directive, Keyword, Operator, Position, Span, TokenType};
+
+impl Parser {
+    pub(super) fn parse_statement(&mut self) -> Result<Statement, SyntaxError> {
+        let start = self.start();
+        let kind = self.parse_statement_kind()?;
+
+        Ok(Statement {
+            kind,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_statement_kind(&mut self) -> Result<StatementKind, SyntaxError> {
+        if let Some(TokenType::Directive(directive)) = self.peek_type() {
+            let directive = *directive;
+            return Ok(self.parse_directive(directive));
+        }
+
+        let is_callback =
+            matches!(self.peek_type(), Some(TokenType::Identifier(name)) if name == "CALLBACK");
+
+        if is_callback && self.peek_keyword_nth(1) == Some(Keyword::Function) {
+            self.bump();
+            return self.parse_routine();
+        }
+
+        let keyword = match self.peek_keyword() {
+            Some(keyword) => keyword,
+            None => return self.parse_assignment_or_call(),
+        };
+
+        match keyword {
+            Keyword::Dim | Keyword::Local | Keyword::Global | Keyword::Static | Keyword::Redim => {
+                self.parse_declaration(keyword)
+            }
+            Keyword::Begin => {
+                let opener = self.bump_opener();
+                self.expect_keyword(Keyword::Const, "CONST")?;
+                let body = self.parse_block(&CONST_ENDS, opener);
+                self.expect_block_end(BlockEnd::EndConst, opener);
+
+                Ok(StatementKind::ConstBlock { body })
+            }
+            Keyword::If => self.parse_if(),
+            Keyword::For => self.parse_for(),
+            Keyword::While => {
+                let opener = self.bump_opener();
+                let condition = self.parse_expression()?;
+                let body = self.parse_block(&WHILE_ENDS, opener);
+                self.expect_block_end(BlockEnd::Wend, opener);
+
+                Ok(StatementKind::While { condition, body })
+            }
+            Keyword::Do => {
+                let opener = self.bump_opener();
+                let condition = self.parse_loop_condition()?;
+                let body = self.parse_block(&DO_ENDS, opener);
+                self.expect_block_end(BlockEnd::Loop, opener);
+                let result = self.parse_loop_condition();
+                let loop_condition = self.recover(result).flatten();
+
+                Ok(StatementKind::Do {
+                    condition,
+                    body,
+                    loop_condition,
+                })
+            }
+            Keyword::Select => self.parse_select(),
+            Keyword::With => {
+                let opener = self.bump_opener();
+                let object = self.parse_expression()?;
+                let body = self.parse_block(&WITH_ENDS, opener);
+                self.expect_block_end(BlockEnd::EndWith, opener);
+
+                Ok(StatementKind::With { object, body })
+            }
+            // FUNCTION = value sets the result of the function
+            Keyword::Function
+                if self.peek_nth(1).map(|token| &token.token_type)
+                    == Some(&TokenType::EqualSign) =>
+            {
+                let target = self.bump_name();
+                self.parse_assignment(Expression {
+                    span: target.span,
+                    kind: ExpressionKind::Name(target),
+                })
+            }
+            Keyword::Function | Keyword::Sub => self.parse_routine(),
+            Keyword::Type => self.parse_type(),
+            Keyword::Declare => self.parse_declare(),
+            Keyword::Alias => Ok(self.parse_alias()),
+            Keyword::Exit => {
+                self.bump();
+                Ok(StatementKind::Exit(self.parse_optional_keyword()))
+            }
+            Keyword::Iterate => {
+                self.bump();
+                Ok(StatementKind::Iterate(self.parse_optional_keyword()))
+            }
+            Keyword::Return => {
+                self.bump();
+
+                if self.at_statement_end() {
+                    Ok(StatementKind::Return(None))
+                } else {
+                    Ok(StatementKind::Return(Some(self.parse_expression()?)))
+                }
+            }
+            Keyword::Goto => {
+                self.bump();
+                Ok(StatementKind::Goto(self.expect_name("label")?))
+            }
+            Keyword::Gosub => {
+                self.bump();
+                Ok(StatementKind::Gosub(self.expect_name("label")?))
+            }
+            Keyword::Call | Keyword::Let => {
+                self.bump();
+                self.parse_assignment_or_call()
+            }
+            // Keywords which behave as built-in procedures
+            Keyword::Incr | Keyword::Decr => {
+                let name = self.bump_name();
+                let arguments = self.parse_statement_arguments()?;
+
+                Ok(StatementKind::Call {
+                    target: Expression {
+                        span: name.span,
+                        kind: ExpressionKind::Name(name),
+                    },
+                    arguments,
+                })
+            }
+            _ => self.parse_assignment_or_call(),
+        }
+    }
+
+    /// Directive keeps the rest of its line as written, #COMPILED skips the whole section
+    fn parse_directive(&mut self, directive: Directive) -> StatementKind {
+        self.bump();
+
+        if directive == Directive::Compiled {
+            while let Some(token) = self.bump() {
+                if token.token_type == TokenType::Directive(Directive::EndCompiled) {
+                    break;
+                }
+            }
+
+            return StatementKind::CompiledSection;
+        }
+
+        let mut arguments = String::new();
+
+        while !self.at_line_end() {
+            let token = &self.tokens[self.index];
+
+            // Whitespace is kept as written, comments are left out
+            for part in token
+                .leading_trivia
+                .iter()
+                .chain(Some(&token.token))
+                .chain(token.trailing_trivia.iter())
+                .filter(|part| !matches!(part.token_type, TokenType::Comment(_)))
+            {
+                arguments.push_str(&part.text);
+            }
+
+            self.bump();
+        }
+
+        StatementKind::Directive {
+            directive,
+            arguments: arguments.trim().to_string(),
+        }
+    }
+
+    /// Statement starting by name, which is assignment, procedure call or equate definition
+    fn parse_assignment_or_call(&mut self) -> Result<StatementKind, SyntaxError> {
+        if let Some(TokenType::Equate(_)) = self.peek_type() {
+            let name = self.bump_name();
+            self.expect(&TokenType::EqualSign, "'='")?;
+
+            return Ok(StatementKind::Equate {
+                name,
+                value: self.parse_expression()?,
+            });
+        }
+
+        if !matches!(
+            self.peek_type(),
+            Some(TokenType::Identifier(_)) | Some(TokenType::Dot)
+        ) {
+            return Err(self.error("statement"));
+        }
+
+        // Procedure name, possibly with object, such as Console_Write or .Method
+        let callee = self.parse_callee()?;
+        let after_callee = (self.index, self.previous_end);
+
+        if let Ok(target) = self.parse_postfix(callee.clone()) {
+            match self.peek_type() {
+                Some(TokenType::EqualSign) | Some(TokenType::CompoundAssignment(_)) => {
+                    return self.parse_assignment(target)
+                }
+                _ if self.at_statement_end() => {
+                    return Ok(match target.kind {
+                        ExpressionKind::Call { target, arguments } => StatementKind::Call {
+                            target: *target,
+                            arguments,
+                        },
+                        _ => StatementKind::Call {
+                            target,
+                            arguments: vec![],
+                        },
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // Arguments without parentheses, where the first one may start by parenthesis
+        self.index = after_callee.0;
+        self.previous_end = after_callee.1;
+
+        Ok(StatementKind::Call {
+            target: callee,
+            arguments: self.parse_statement_arguments()?,
+        })
+    }
+
+    /// Name with members, but without parentheses, which may start the first argument
+    fn parse_callee(&mut self) -> Result<Expression, SyntaxError> {
+        let start = self.start();
+
+        let mut expression = if self.eat(&TokenType::Dot) {
+            let member = self.expect_word("member name")?;
+            Expression {
+                span: self.span_from(start),
+                kind: ExpressionKind::Member {
+                    object: None,
+                    member,
+                },
+            }
+        } else {
+            let name = self.expect_name("statement")?;
+            Expression {
+                span: name.span,
+                kind: ExpressionKind::Name(name),
+            }
+        };
+
+        while self.peek_type() == Some(&TokenType::Dot) {
+            self.bump();
+            let member = self.expect_word("member name")?;
+
+            expression = Expression {
+                span: self.span_from(start),
+                kind: ExpressionKind::Member {
+                    object: Some(Box::new(expression)),
+                    member,
+                },
+            };
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_assignment(&mut self, target: Expression) -> Result<StatementKind, SyntaxError> {
+        let operator = match self.bump().map(|token| token.token_type) {
+            Some(TokenType::EqualSign) => None,
+            Some(TokenType::CompoundAssignment(operator)) => Some(operator),
+            _ => return Err(self.error("'='")),
+        };
+
+        Ok(StatementKind::Assignment {
+            target,
+            operator,
+            value: self.parse_expression()?,
+        })
+    }
+
+    /// Comma separated arguments up to the end of statement
+    fn parse_statement_arguments(&mut self) -> Result<Vec<Expression>, SyntaxError> {
+        let mut arguments = vec![];
+
+        if self.at_statement_end() {
+            return Ok(arguments);
+        }
+
+        loop {
+            arguments.push(self.parse_expression()?);
+
+            if !self.eat(&TokenType::Comma) {
+                return Ok(arguments);
+            }
+        }
+    }
+
+    fn parse_declaration(&mut self, keyword: Keyword) -> Result<StatementKind, SyntaxError> {
+        self.bump();
+
+        if keyword == Keyword::Redim {
+            self.eat_keyword(Keyword::Preserve);
+        }
+
+        let mut variables = vec![];
+
+        loop {
+            variables.push(self.parse_variable()?);
+
+            if !self.eat(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        Ok(StatementKind::Declaration { keyword, variables })
+    }
+
+    /// Name [(dimensions)] [AS type] [AT address] [= value]
+    fn parse_variable(&mut self) -> Result<Variable, SyntaxError> {
+        let name = self.expect_name("variable name")?;
+        let mut dimensions = vec![];
+
+        if self.eat(&TokenType::Paren('(')) && !self.eat(&TokenType::Paren(')')) {
+            loop {
+                let bound = self.parse_expression()?;
+
+                dimensions.push(if self.eat_keyword(Keyword::To) {
+                    Dimension {
+                        lower: Some(bound),
+                        upper: self.parse_expression()?,
+                    }
+                } else {
+                    Dimension {
+                        lower: None,
+                        upper: bound,
+                    }
+                });
+
+                if !self.eat(&TokenType::Comma) {
+                    break;
+                }
+            }
+
+            self.expect(&TokenType::Paren(')'), "')'")?;
+        }
+
+        let data_type = self.parse_data_type()?;
+
+        let at = if self.eat_keyword(Keyword::At) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        let value = if self.eat(&TokenType::EqualSign) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        Ok(Variable {
+            name,
+            dimensions,
+            data_type,
+            at,
+            value,
+        })
+    }
+
+    /// Optional AS type, such as AS LONG or AS STRING * 10
+    fn parse_data_type(&mut self) -> Result<Option<DataType>, SyntaxError> {
+        if !self.eat_keyword(Keyword::As) {
+            return Ok(None);
+        }
+
+        self.eat_keyword(Keyword::New);
+        let name = self.expect_word("type name")?;
+
+        let length = if self.eat(&TokenType::Operator(Operator::Multiply)) {
+            Some(self.parse_postfix_expression()?)
+        } else {
+            None
+        };
+
+        let pointer = self.eat_keyword(Keyword::Ptr);
+
+        Ok(Some(DataType {
+            name,
+            length,
+            pointer,
+        }))
+    }
+
+    fn parse_if(&mut self) -> Result<StatementKind, SyntaxError> {
+        let opener = self.bump_opener();
+        let mut condition = self.parse_expression()?;
+        self.expect_keyword(Keyword::Then, "THEN")?;
+
+        if !self.at_line_end() {
+            self.single_line_if += 1;
+            let result = self.parse_single_line_if(condition);
+            self.single_line_if -= 1;
+
+            return result;
+        }
+
+        let mut branches = vec![];
+
+        loop {
+            let body = self.parse_block(&IF_ENDS, opener);
+            branches.push(ConditionalBlock { condition, body });
+
+            if !self.eat_keyword(Keyword::ElseIf) {
+                break;
+            }
+
+            let start = self.start();
+            let result = self.parse_expression().and_then(|condition| {
+                self.expect_keyword(Keyword::Then, "THEN")?;
+                Ok(condition)
+            });
+
+            condition = self
+                .recover(result)
+                .unwrap_or_else(|| self.error_expression(start));
+        }
+
+        let else_body = if self.eat_keyword(Keyword::Else) {
+            Some(self.parse_block(&ELSE_ENDS, opener))
+        } else {
+            None
+        };
+
+        self.expect_block_end(BlockEnd::EndIf, opener);
+
+        Ok(StatementKind::If {
+            branches,
+            else_body,
+        })
+    }
+
+    /// IF condition THEN statements [ELSE statements], all on one line
+    fn parse_single_line_if(
+        &mut self,
+        condition: Expression,
+    ) -> Result<StatementKind, SyntaxError> {
+        let body = self.parse_line_statements()?;
+
+        let else_body = if self.eat_keyword(Keyword::Else) {
+            Some(self.parse_line_statements()?)
+        } else {
+            None
+        };
+
+        Ok(StatementKind::If {
+            branches: vec![ConditionalBlock { condition, body }],
+            else_body,
+        })
+    }
+
+    /// Statements separated by colons up to the end of line or ELSE of single line IF
+    fn parse_line_statements(&mut self) -> Result<Vec<Statement>, SyntaxError> {
+        let mut statements = vec![];
+
+        while !self.at_line_end() && self.peek_keyword() != Some(Keyword::Else) {
+            statements.push(self.parse_statement()?);
+
+            if !self.at_statement_end() {
+                return Err(self.error("end of statement"));
+            }
+
+            self.eat(&TokenType::Colon);
+        }
+
+        Ok(statements)
+    }
+
+    fn parse_for(&mut self) -> Result<StatementKind, SyntaxError> {
+        let opener = self.bump_opener();
+
+        if self.eat_keyword(Keyword::Each) {
+            let variable = self.expect_name("loop variable")?;
+            self.expect_keyword(Keyword::In, "IN")?;
+            let collection = self.parse_expression()?;
+
+            let body = self.parse_block(&FOR_ENDS, opener);
+            self.expect_block_end(BlockEnd::Next, opener);
+            self.eat_next_variable();
+
+            return Ok(StatementKind::ForEach {
+                variable,
+                collection,
+                body,
+            });
+        }
+
+        let variable = self.expect_name("loop variable")?;
+        self.expect(&TokenType::EqualSign, "'='")?;
+        let from = self.parse_expression()?;
+        self.expect_keyword(Keyword::To, "TO")?;
+        let to = self.parse_expression()?;
+
+        let step = if self.eat_keyword(Keyword::Step) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        let body = self.parse_block(&FOR_ENDS, opener);
+        self.expect_block_end(BlockEnd::Next, opener);
+        self.eat_next_variable();
+
+        Ok(StatementKind::For {
+            variable,
+            from,
+            to,
+            step,
+            body,
+        })
+    }
+
+    /// NEXT can repeat the loop variable
+    fn eat_next_variable(&mut self) {
+        if let Some(TokenType::Identifier(_)) = self.peek_type() {
+            self.bump();
+        }
+    }
+
+    /// WHILE or UNTIL condition after DO or LOOP
+    fn parse_loop_condition(&mut self) -> Result<Option<LoopCondition>, SyntaxError> {
+        let until = match self.peek_keyword() {
+            Some(Keyword::While) => false,
+            Some(Keyword::Until) => true,
+            _ => return Ok(None),
+        };
+
+        self.bump();
+
+        Ok(Some(LoopCondition {
+            until,
+            condition: self.parse_expression()?,
+        }))
+    }
+
+    fn parse_select(&mut self) -> Result<StatementKind, SyntaxError> {
+        let opener = self.bump_opener();
+        self.expect_keyword(Keyword::Case, "CASE")?;
+        let subject = self.parse_expression()?;
+
+        if !self.at_statement_end() {
+            return Err(self.error("end of statement"));
+        }
+
+        self.skip_separators();
+
+        if self.peek().is_some() && self.peek_block_end().is_none() {
+            let error = self.error("CASE");
+            self.errors.push(error);
+
+            // Statements before the first CASE are parsed only to get past them
+            self.parse_block(&SELECT_ENDS, opener);
+        }
+
+        let mut cases = vec![];
+        let mut else_body = None;
+
+        while self.eat_keyword(Keyword::Case) {
+            if self.eat_keyword(Keyword::Else) {
+                else_body = Some(self.parse_block(&SELECT_ENDS, opener));
+                continue;
+            }
+
+            let result = self.parse_case_items();
+            let items = self.recover(result).unwrap_or_default();
+            let body = self.parse_block(&SELECT_ENDS, opener);
+
+            cases.push(Case { items, body });
+        }
+
+        self.expect_block_end(BlockEnd::EndSelect, opener);
+
+        Ok(StatementKind::Select {
+            subject,
+            cases,
+            else_body,
+        })
+    }
+
+    /// Values after CASE, such as 1, 5 TO 10, IS > 20
+    fn parse_case_items(&mut self) -> Result<Vec<CaseItem>, SyntaxError> {
+        let mut items = vec![];
+
+        loop {
+            let is_comparison =
+                matches!(self.peek_type(), Some(TokenType::Identifier(name)) if name == "IS");
+
+            if is_comparison {
+                self.bump();
+
+                let operator = match self.peek_type() {
+                    Some(TokenType::Comparator(comparator)) => {
+                        BinaryOperator::Comparator(*comparator)
+                    }
+                    Some(TokenType::EqualSign) => BinaryOperator::Equal,
+                    _ => return Err(self.error("comparison")),
+                };
+
+                self.bump();
+                items.push(CaseItem::Comparison(operator, self.parse_expression()?));
+            } else {
+                let value = self.parse_expression()?;
+
+                if self.eat_keyword(Keyword::To) {
+                    items.push(CaseItem::Range(value, self.parse_expression()?));
+                } else {
+                    items.push(CaseItem::Value(value));
+                }
+            }
+
+            if !self.eat(&TokenType::Comma) {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// FUNCTION or SUB with its body, optionally preceded by CALLBACK
+    fn parse_routine(&mut self) -> Result<StatementKind, SyntaxError> {
+        let keyword = self.peek_keyword().unwrap_or(Keyword::Function);
+        let opener = self.bump_opener();
+
+        let name = self.expect_name("routine name")?;
+        let parameters = self.parse_parameters()?;
+        let return_type = self.parse_data_type()?;
+        let body = if keyword == Keyword::Sub {
+            let body = self.parse_block(&SUB_ENDS, opener);
+            self.expect_block_end(BlockEnd::EndSub, opener);
+            body
+        } else {
+            let body = self.parse_block(&FUNCTION_ENDS, opener);
+            self.expect_block_end(BlockEnd::EndFunction, opener);
+            body
+        };
+
+        Ok(StatementKind::Routine(Routine {
+            keyword,
+            name,
+            parameters,
+            return_type,
+            body,
+        }))
+    }
+
+    /// Optional parameter list in parentheses
+    fn parse_parameters(&mut self) -> Result<Vec<Parameter>, SyntaxError> {
+        let mut parameters = vec![];
+
+        if !self.eat(&TokenType::Paren('(')) || self.eat(&TokenType::Paren(')')) {
+            return Ok(parameters);
+        }
+
+        loop {
+            let mut optional = self.eat_keyword(Keyword::Optional);
+
+            let passing = match self.peek_keyword() {
+                Some(keyword @ Keyword::Byval) | Some(keyword @ Keyword::Byref) => {
+                    self.bump();
+                    Some(keyword)
+                }
+                _ => None,
+            };
+
+            optional |= self.eat_keyword(Keyword::Optional);
+
+            let name = self.expect_name("parameter name")?;
+
+            let array = self.eat(&TokenType::Paren('('));
+            if array {
+                self.expect(&TokenType::Paren(')'), "')'")?;
+            }
+
+            let data_type = self.parse_data_type()?;
+
+            let default = if self.eat(&TokenType::EqualSign) {
+                Some(self.parse_expression()?)
+            } else {
+                None
+            };
+
+            parameters.push(Parameter {
+                passing,
+                optional,
+                name,
+                array,
+                data_type,
+                default,
+            });
+
+            if !self.eat(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.expect(&TokenType::Paren(')'), "')'")?;
+
+        Ok(parameters)
+    }
+
+    fn parse_type(&mut self) -> Result<StatementKind, SyntaxError> {
+        let opener = self.bump_opener();
+        let name = self.expect_name("type name")?;
+
+        // Alignment, such as TYPE Point BYTE
+        if !self.at_statement_end() {
+            self.expect_word("end of statement")?;
+        }
+
+        let members = self.parse_members(BlockEnd::EndType, opener);
+
+        Ok(StatementKind::Type { name, members })
+    }
+
+    /// Members of TYPE or UNION up to the given end, nested UNION blocks included
+    fn parse_members(&mut self, end: BlockEnd, opener: Span) -> Vec<Variable> {
+        let mut members = vec![];
+
+        loop {
+            self.skip_separators();
+
+            if self.peek().is_none() || self.peek_block_end().is_some() {
+                break;
+            }
+
+            if self.peek_keyword() == Some(Keyword::Union) {
+                let union_opener = self.bump_opener();
+
+                if !self.at_statement_end() {
+                    let error = self.error("end of statement");
+                    self.errors.push(error);
+                    self.skip_statement();
+                }
+
+                members.extend(self.parse_members(BlockEnd::EndUnion, union_opener));
+                continue;
+            }
+
+            let result = self.parse_variable().and_then(|member| {
+                if self.at_statement_end() {
+                    Ok(member)
+                } else {
+                    Err(self.error("end of statement"))
+                }
+            });
+
+            members.extend(self.recover(result));
+        }
+
+        self.expect_block_end(end, opener);
+
+        members
+    }
+
+    /// DECLARE FUNCTION|SUB name LIB "library" [ALIAS "name"] [(parameters)] [AS type]
+    fn parse_declare(&mut self) -> Result<StatementKind, SyntaxError> {
+        self.bump();
+
+        let keyword = match self.peek_keyword() {
+            Some(keyword @ Keyword::Function) | Some(keyword @ Keyword::Sub) => keyword,
+            _ => return Err(self.error("FUNCTION or SUB")),
+        };
+
+        self.bump();
+        let name = self.expect_name("routine name")?;
+
+        let library = if self.eat_keyword(Keyword::Lib) {
+            Some(self.expect_text("library name")?)
+        } else {
+            None
+        };
+
+        let alias = if self.eat_keyword(Keyword::Alias) {
+            Some(self.expect_text("exported name")?)
+        } else {
+            None
+        };
+
+        let parameters = self.parse_parameters()?;
+        let return_type = self.parse_data_type()?;
+
+        Ok(StatementKind::Declare(Declare {
+            keyword,
+            name,
+            library,
+            alias,
+            parameters,
+            return_type,
+        }))
+    }
+
+    fn expect_text(&mut self, expected: &str) -> Result<String, SyntaxError> {
+        match self.peek_type() {
+            Some(TokenType::Text(text)) => {
+                let text = text.clone();
+                self.bump();
+                Ok(text)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// ALIAS keyword AS name, registering the name for the rest of the script
+    ///
+    /// Malformed aliases are skipped, as the alias-syntax rule explains what is wrong.
+    fn parse_alias(&mut self) -> StatementKind {
+        self.bump();
+
+        let keyword = match self.peek_type() {
+            Some(TokenType::Keyword(_)) => Some(self.bump_name()),
+            _ => None,
+        };
+
+        let alias = if keyword.is_some() && self.eat_keyword(Keyword::As) {
+            self.expect_name("alias name").ok()
+        } else {
+            None
+        };
+
+        if let (Some(keyword), Some(alias)) = (&keyword, &alias) {
+            if let Some(aliased) = Keyword::from_name(&keyword.name) {
+                self.aliases.push((alias.name.clone(), aliased));
+            }
+        }
+
+        while !self.at_statement_end() {
+            self.bump();
+        }
+
+        StatementKind::Alias { keyword, alias }
+    }
+
+    /// Reports the error and skips the rest of the statement, so the block goes on
+    fn recover<T>(&mut self, result: Result<T, SyntaxError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(error);
+                self.skip_statement();
+                None
+            }
+        }
+    }
+
+    fn error_expression(&self, start: Position) -> Expression {
+        Expression {
+            kind: ExpressionKind::Error,
+            span: Span {
+                start,
+                end: self.previous_end.max(start),
+            },
+        }
+    }
+
+    /// Keyword after EXIT or ITERATE, such as FOR
+    fn parse_optional_keyword(&mut self) -> Option<Keyword> {
+        if self.at_statement_end() {
+            return None;
+        }
+
+        let keyword = self.peek_keyword()?;
+        self.bump();
+
+        Some(keyword)
+    }
+}
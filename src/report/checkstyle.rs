@@ -0,0 +1,103 @@
+use super::{escape_xml, ScriptFailure, ScriptReport};
+use crate::thinbasic_script::Severity;
+
+pub fn print(reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    print!("{}", render(reports, failures));
+}
+
+fn render(reports: &[ScriptReport], failures: &[ScriptFailure]) -> String {
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<checkstyle version=\"4.3\">\n");
+
+    for report in reports {
+        xml.push_str(&format!(
+            "  <file name=\"{}\">\n",
+            escape_xml(&report.script_file)
+        ));
+
+        for issue in report.reported_issues() {
+            xml.push_str(&format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"thinclippy.{}\"/>\n",
+                issue.line,
+                issue.pos,
+                severity(issue.severity),
+                escape_xml(&issue.summary),
+                escape_xml(issue.code)
+            ));
+        }
+
+        xml.push_str("  </file>\n");
+    }
+
+    // Scripts which could not be read have no line to point at
+    for failure in failures {
+        xml.push_str(&format!(
+            "  <file name=\"{}\">\n    <error line=\"0\" severity=\"error\" message=\"{}\" source=\"thinclippy\"/>\n  </file>\n",
+            escape_xml(&failure.script_file),
+            escape_xml(&failure.message)
+        ));
+    }
+
+    xml.push_str("</checkstyle>\n");
+
+    xml
+}
+
+/// Checkstyle knows just error, warning, info and ignore
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Hint | Severity::Info => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::report::{ScriptFailure, ScriptReport};
+    use crate::thinbasic_script::{Encoding, IssueSummary, Severity};
+
+    #[test]
+    fn every_file_is_listed() {
+        let mut issue = IssueSummary::new("a.tbasic", 2, 7, "Use \"AS\" & <name>");
+        issue.set_rule("TC0001", "alias-syntax", Severity::Hint);
+
+        let reports = [
+            ScriptReport {
+                script_file: "a.tbasic".to_string(),
+                source: String::new(),
+                encoding: Encoding::Utf8,
+                issues: vec![issue],
+            },
+            ScriptReport {
+                script_file: "b.tbasic".to_string(),
+                source: String::new(),
+                encoding: Encoding::Utf8,
+                issues: vec![],
+            },
+        ];
+
+        let failures = [ScriptFailure {
+            script_file: "c.tbasic".to_string(),
+            message: "c.tbasic: permission denied".to_string(),
+        }];
+
+        assert_eq!(
+            render(&reports, &failures),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <checkstyle version=\"4.3\">\n  \
+             <file name=\"a.tbasic\">\n    \
+             <error line=\"2\" column=\"7\" severity=\"info\" message=\"Use &quot;AS&quot; &amp; &lt;name&gt;\" source=\"thinclippy.TC0001\"/>\n  \
+             </file>\n  \
+             <file name=\"b.tbasic\">\n  \
+             </file>\n  \
+             <file name=\"c.tbasic\">\n    \
+             <error line=\"0\" severity=\"error\" message=\"c.tbasic: permission denied\" source=\"thinclippy\"/>\n  \
+             </file>\n\
+             </checkstyle>\n"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+
+use super::{ScriptFailure, ScriptReport, Summary};
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    issues: Vec<JsonIssue<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<JsonFailure<'a>>,
+    summary: Summary,
+}
+
+#[derive(Serialize)]
+struct JsonIssue<'a> {
+    file: &'a str,
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    code: &'a str,
+    rule: &'a str,
+    severity: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related: Vec<JsonRelated<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFailure<'a> {
+    file: &'a str,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonRelated<'a> {
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    message: &'a str,
+}
+
+pub fn print(reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    println!("{}", render(reports, failures));
+}
+
+fn render(reports: &[ScriptReport], failures: &[ScriptFailure]) -> String {
+    let issues = reports
+        .iter()
+        .flat_map(|report| report.reported_issues())
+        .map(|issue| JsonIssue {
+            file: &issue.script_file,
+            line: issue.line,
+            column: issue.pos,
+            end_line: issue.end_line,
+            end_column: issue.end_pos,
+            code: issue.code,
+            rule: issue.rule_name,
+            severity: issue.severity.as_str(),
+            message: &issue.summary,
+            related: issue
+                .related
+                .iter()
+                .map(|related| JsonRelated {
+                    line: related.line,
+                    column: related.pos,
+                    end_line: related.end_line,
+                    end_column: related.end_pos,
+                    message: &related.message,
+                })
+                .collect(),
+        })
+        .collect();
+
+    let json_report = JsonReport {
+        issues,
+        failures: failures
+            .iter()
+            .map(|failure| JsonFailure {
+                file: &failure.script_file,
+                message: &failure.message,
+            })
+            .collect(),
+        summary: Summary::new(reports, failures),
+    };
+
+    // Serialization of plain structures with string keys cannot fail
+    serde_json::to_string_pretty(&json_report).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::report::{ScriptFailure, ScriptReport};
+    use crate::thinbasic_script::{Encoding, IssueSummary, Severity};
+
+    #[test]
+    fn issues_and_summary_are_rendered() {
+        let mut issue = IssueSummary::new("test.tbasic", 2, 7, "Message with \"quotes\"");
+        issue.set_rule("TC0001", "alias-syntax", Severity::Warning);
+
+        let mut suppressed = IssueSummary::new("test.tbasic", 3, 1, "Hidden");
+        suppressed.suppressed = true;
+
+        let reports = [ScriptReport {
+            script_file: "test.tbasic".to_string(),
+            source: String::new(),
+            encoding: Encoding::Utf8,
+            issues: vec![issue, suppressed],
+        }];
+
+        let json: serde_json::Value = serde_json::from_str(&render(&reports, &[])).unwrap();
+
+        assert_eq!(json["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(json["issues"][0]["file"], "test.tbasic");
+        assert_eq!(json["issues"][0]["line"], 2);
+        assert_eq!(json["issues"][0]["column"], 7);
+        assert_eq!(json["issues"][0]["code"], "TC0001");
+        assert_eq!(json["issues"][0]["severity"], "warning");
+        assert_eq!(json["issues"][0]["message"], "Message with \"quotes\"");
+        assert_eq!(json["summary"]["warnings"], 1);
+        assert_eq!(json["summary"]["suppressed"], 1);
+        assert!(json.get("failures").is_none());
+    }
+
+    #[test]
+    fn failed_scripts_are_listed() {
+        let failures = [ScriptFailure {
+            script_file: "missing.tbasic".to_string(),
+            message: "missing.tbasic: file not found, please check the path".to_string(),
+        }];
+
+        let json: serde_json::Value = serde_json::from_str(&render(&[], &failures)).unwrap();
+
+        assert_eq!(json["failures"][0]["file"], "missing.tbasic");
+        assert_eq!(json["summary"]["failed"], 1);
+    }
+}
@@ -0,0 +1,122 @@
+use super::{escape_xml, ScriptFailure, ScriptReport};
+
+/// Every analysed script is a test case, which fails when any issue was reported for it
+///
+/// Scripts which could not be analysed at all are test cases ending by an error.
+pub fn print(reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    print!("{}", render(reports, failures));
+}
+
+fn render(reports: &[ScriptReport], failures: &[ScriptFailure]) -> String {
+    let failed_reports = reports
+        .iter()
+        .filter(|report| report.reported_issues().next().is_some())
+        .count();
+
+    let mut xml = String::new();
+
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"thinClippy\" tests=\"{}\" failures=\"{}\" errors=\"{}\">\n",
+        reports.len() + failures.len(),
+        failed_reports,
+        failures.len()
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"thinClippy\" tests=\"{}\" failures=\"{}\" errors=\"{}\">\n",
+        reports.len() + failures.len(),
+        failed_reports,
+        failures.len()
+    ));
+
+    for report in reports {
+        let issues: Vec<String> = report
+            .reported_issues()
+            .map(|issue| issue.to_string())
+            .collect();
+
+        let name = escape_xml(&report.script_file);
+
+        if issues.is_empty() {
+            xml.push_str(&format!(
+                "    <testcase classname=\"thinClippy\" name=\"{}\"/>\n",
+                name
+            ));
+        } else {
+            xml.push_str(&format!(
+                "    <testcase classname=\"thinClippy\" name=\"{}\">\n",
+                name
+            ));
+            xml.push_str(&format!(
+                "      <failure message=\"{} issue(s) found\" type=\"thinClippy\">{}</failure>\n",
+                issues.len(),
+                escape_xml(&issues.join("\n"))
+            ));
+            xml.push_str("    </testcase>\n");
+        }
+    }
+
+    for failure in failures {
+        xml.push_str(&format!(
+            "    <testcase classname=\"thinClippy\" name=\"{}\">\n",
+            escape_xml(&failure.script_file)
+        ));
+        xml.push_str(&format!(
+            "      <error message=\"{}\" type=\"thinClippy\"/>\n",
+            escape_xml(&failure.message)
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::report::{ScriptFailure, ScriptReport};
+    use crate::thinbasic_script::{Encoding, IssueSummary, Severity};
+
+    #[test]
+    fn scripts_with_issues_fail() {
+        let mut issue = IssueSummary::new("a.tbasic", 2, 7, "Message");
+        issue.set_rule("TC0001", "alias-syntax", Severity::Error);
+
+        let mut suppressed = IssueSummary::new("b.tbasic", 1, 1, "Hidden");
+        suppressed.suppressed = true;
+
+        let reports = [
+            ScriptReport {
+                script_file: "a.tbasic".to_string(),
+                source: String::new(),
+                encoding: Encoding::Utf8,
+                issues: vec![issue],
+            },
+            ScriptReport {
+                script_file: "b.tbasic".to_string(),
+                source: String::new(),
+                encoding: Encoding::Utf8,
+                issues: vec![suppressed],
+            },
+        ];
+
+        let failures = [ScriptFailure {
+            script_file: "c.tbasic".to_string(),
+            message: "c.tbasic: file not found".to_string(),
+        }];
+
+        let xml = render(&reports, &failures);
+
+        assert!(
+            xml.contains("<testsuite name=\"thinClippy\" tests=\"3\" failures=\"1\" errors=\"1\"")
+        );
+        assert!(xml.contains("<error message=\"c.tbasic: file not found\" type=\"thinClippy\"/>"));
+        assert!(xml.contains(
+            "<failure message=\"1 issue(s) found\" type=\"thinClippy\">a.tbasic:2:7: error: Message [TC0001]</failure>"
+        ));
+        assert!(xml.contains("<testcase classname=\"thinClippy\" name=\"b.tbasic\"/>"));
+    }
+}
@@ -0,0 +1,137 @@
+// Every output format lives in its own file
+mod checkstyle;
+mod json;
+mod junit;
+mod sarif;
+mod short;
+mod text;
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::thinbasic_script::{Encoding, IssueSummary, Severity};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Sarif,
+    Short,
+    Checkstyle,
+    Junit,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Format, String> {
+        match text.to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "sarif" => Ok(Format::Sarif),
+            "short" => Ok(Format::Short),
+            "checkstyle" => Ok(Format::Checkstyle),
+            "junit" => Ok(Format::Junit),
+            _ => Err(format!(
+                "Unknown format '{}', expected text, json, sarif, short, checkstyle or junit",
+                text
+            )),
+        }
+    }
+}
+
+/// Outcome of the analysis of a single script
+pub struct ScriptReport {
+    pub script_file: String,
+    pub source: String,
+    pub encoding: Encoding,
+
+    /// All the issues found, including the suppressed ones
+    pub issues: Vec<IssueSummary>,
+}
+
+impl ScriptReport {
+    /// Issues which were not suppressed by a comment
+    pub fn reported_issues(&self) -> impl Iterator<Item = &IssueSummary> {
+        self.issues.iter().filter(|issue| !issue.suppressed)
+    }
+}
+
+/// Script which could not be analysed, such as file which could not be read
+pub struct ScriptFailure {
+    pub script_file: String,
+    pub message: String,
+}
+
+/// Issue counts over all the analysed scripts
+#[derive(Debug, Default, Serialize)]
+pub struct Summary {
+    pub files: usize,
+    pub issues: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub hints: usize,
+    pub infos: usize,
+    pub suppressed: usize,
+    /// Scripts which could not be analysed
+    pub failed: usize,
+}
+
+impl Summary {
+    pub fn new(reports: &[ScriptReport], failures: &[ScriptFailure]) -> Summary {
+        let mut summary = Summary {
+            files: reports.len(),
+            failed: failures.len(),
+            ..Summary::default()
+        };
+
+        for issue in reports.iter().flat_map(|report| report.issues.iter()) {
+            if issue.suppressed {
+                summary.suppressed += 1;
+                continue;
+            }
+
+            summary.issues += 1;
+
+            match issue.severity {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Hint => summary.hints += 1,
+                Severity::Info => summary.infos += 1,
+            }
+        }
+
+        summary
+    }
+}
+
+/// Prints the reports, failed scripts are listed where the format has a place for them
+pub fn print(format: Format, reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    match format {
+        Format::Text => text::print(reports, failures),
+        Format::Json => json::print(reports, failures),
+        Format::Sarif => sarif::print(reports, failures),
+        Format::Short => short::print(reports),
+        Format::Checkstyle => checkstyle::print(reports, failures),
+        Format::Junit => junit::print(reports, failures),
+    }
+}
+
+/// Escapes text for use in XML attributes and elements
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
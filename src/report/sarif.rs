@@ -0,0 +1,298 @@
+use serde_json::{json, Value};
+
+use super::{ScriptFailure, ScriptReport};
+use crate::rules;
+use crate::thinbasic_script::Severity;
+
+const SCHEMA_URI: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/petrSchreiber/thinclippy";
+
+pub fn print(reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    println!("{}", render(reports, failures));
+}
+
+fn render(reports: &[ScriptReport], failures: &[ScriptFailure]) -> String {
+    let descriptors: Vec<(&'static str, &'static str, &'static str, Severity)> = rules::registry()
+        .iter()
+        .map(|rule| {
+            (
+                rule.id(),
+                rule.name(),
+                rule.description(),
+                rule.default_severity(),
+            )
+        })
+        .collect();
+
+    let sarif_rules: Vec<Value> = descriptors
+        .iter()
+        .map(|&(id, name, description, severity)| {
+            json!({
+                "id": id,
+                "name": name,
+                "shortDescription": { "text": description },
+                "help": {
+                    "text": format!(
+                        "{}. Intentional cases can be suppressed by comment ' thinclippy:ignore {}",
+                        description, id
+                    )
+                },
+                "defaultConfiguration": { "level": level(severity) }
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = reports
+        .iter()
+        .flat_map(|report| report.issues.iter())
+        .map(|issue| {
+            let mut result = json!({
+                "ruleId": issue.code,
+                "level": level(issue.severity),
+                "message": { "text": issue.summary },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": file_uri(&issue.script_file) },
+                        "region": {
+                            "startLine": issue.line,
+                            "startColumn": issue.pos.max(1),
+                            "endLine": issue.end_line,
+                            // SARIF end column points after the last character
+                            "endColumn": issue.end_pos.max(1) + 1
+                        }
+                    }
+                }]
+            });
+
+            if let Some(index) = descriptors.iter().position(|d| d.0 == issue.code) {
+                result["ruleIndex"] = json!(index);
+            }
+
+            if issue.suppressed {
+                result["suppressions"] = json!([{ "kind": "inSource" }]);
+            }
+
+            if !issue.related.is_empty() {
+                let related_locations: Vec<Value> = issue
+                    .related
+                    .iter()
+                    .enumerate()
+                    .map(|(id, related)| {
+                        json!({
+                            "id": id,
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": file_uri(&issue.script_file) },
+                                "region": {
+                                    "startLine": related.line,
+                                    "startColumn": related.pos.max(1),
+                                    "endLine": related.end_line,
+                                    "endColumn": related.end_pos.max(1) + 1
+                                }
+                            },
+                            "message": { "text": related.message }
+                        })
+                    })
+                    .collect();
+
+                result["relatedLocations"] = json!(related_locations);
+            }
+
+            result
+        })
+        .collect();
+
+    let artifacts: Vec<Value> = reports
+        .iter()
+        .map(|report| {
+            json!({
+                "location": { "uri": file_uri(&report.script_file) },
+                "encoding": report.encoding.as_str()
+            })
+        })
+        .collect();
+
+    // Scripts which could not be analysed are reported as notifications of the run
+    let notifications: Vec<Value> = failures
+        .iter()
+        .map(|failure| {
+            json!({
+                "level": "error",
+                "message": { "text": failure.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": file_uri(&failure.script_file) }
+                    }
+                }]
+            })
+        })
+        .collect();
+
+    let sarif = json!({
+        "$schema": SCHEMA_URI,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "thinClippy",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": sarif_rules
+                }
+            },
+            "invocations": [{
+                "executionSuccessful": failures.is_empty(),
+                "toolExecutionNotifications": notifications
+            }],
+            "artifacts": artifacts,
+            "results": results
+        }]
+    });
+
+    // Serialization of plain JSON values cannot fail
+    serde_json::to_string_pretty(&sarif).unwrap()
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Hint | Severity::Info => "note",
+    }
+}
+
+/// SARIF expects URI references, absolute paths become file URIs, relative ones stay relative
+fn file_uri(script_file: &str) -> String {
+    let path = script_file.replace('\\', "/");
+    let bytes = path.as_bytes();
+
+    if let Some(unc_path) = path.strip_prefix("//") {
+        format!("file://{}", encode_path(unc_path))
+    } else if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        format!("file:///{}:{}", &path[..1], encode_path(&path[2..]))
+    } else if path.starts_with('/') {
+        format!("file://{}", encode_path(&path))
+    } else {
+        encode_path(&path)
+    }
+}
+
+/// Percent-encodes everything but unreserved characters and path separators
+///
+/// Colon is encoded too, so that the first segment of relative path cannot pass for scheme.
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(char::from(byte))
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_uri, render};
+    use crate::report::{ScriptFailure, ScriptReport};
+    use crate::thinbasic_script::{Encoding, IssueSummary, Severity};
+    use crate::tokenizer::{Position, Span};
+
+    #[test]
+    fn results_reference_rules_and_suppressions() {
+        let mut issue = IssueSummary::new("scripts\\my test.tbasic", 2, 7, "Message");
+        issue.set_rule("TC0002", "compiled-section", Severity::Error);
+        issue.add_related(
+            &Span {
+                start: Position {
+                    offset: 0,
+                    line: 1,
+                    column: 1,
+                },
+                end: Position {
+                    offset: 9,
+                    line: 1,
+                    column: 10,
+                },
+            },
+            "Section starts here",
+        );
+
+        let mut suppressed = IssueSummary::new("scripts\\my test.tbasic", 3, 1, "Hidden");
+        suppressed.set_rule("TC0001", "alias-syntax", Severity::Hint);
+        suppressed.suppressed = true;
+
+        let reports = [ScriptReport {
+            script_file: "scripts\\my test.tbasic".to_string(),
+            source: String::new(),
+            encoding: Encoding::Windows1252,
+            issues: vec![issue, suppressed],
+        }];
+
+        let failures = [ScriptFailure {
+            script_file: "missing.tbasic".to_string(),
+            message: "missing.tbasic: file not found".to_string(),
+        }];
+
+        let sarif: serde_json::Value = serde_json::from_str(&render(&reports, &failures)).unwrap();
+        let run = &sarif["runs"][0];
+
+        let invocation = &run["invocations"][0];
+        assert_eq!(invocation["executionSuccessful"], false);
+        assert_eq!(
+            invocation["toolExecutionNotifications"][0]["message"]["text"],
+            "missing.tbasic: file not found"
+        );
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["artifacts"][0]["encoding"], "windows-1252");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "TC0002");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0]["ruleId"], "TC0002");
+        assert_eq!(results[0]["ruleIndex"], 1);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "scripts/my%20test.tbasic"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"]["startColumn"],
+            7
+        );
+        assert!(results[0].get("suppressions").is_none());
+
+        let related = &results[0]["relatedLocations"][0];
+        assert_eq!(related["message"]["text"], "Section starts here");
+        assert_eq!(related["physicalLocation"]["region"]["startLine"], 1);
+        assert_eq!(related["physicalLocation"]["region"]["endColumn"], 10);
+        assert!(results[1].get("relatedLocations").is_none());
+
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[1]["suppressions"][0]["kind"], "inSource");
+    }
+
+    #[test]
+    fn paths_become_uris() {
+        assert_eq!(file_uri("scripts\\a b.tbasic"), "scripts/a%20b.tbasic");
+        assert_eq!(file_uri("C:\\dir\\a.tbasic"), "file:///C:/dir/a.tbasic");
+        assert_eq!(
+            file_uri("/home/user/a.tbasic"),
+            "file:///home/user/a.tbasic"
+        );
+        assert_eq!(
+            file_uri("\\\\server\\share\\a.tbasic"),
+            "file://server/share/a.tbasic"
+        );
+        assert_eq!(file_uri("a#1%?:.tbasic"), "a%231%25%3F%3A.tbasic");
+        assert_eq!(
+            file_uri("skript/žluťoučký.tbasic"),
+            "skript/%C5%BElu%C5%A5ou%C4%8Dk%C3%BD.tbasic"
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use super::ScriptReport;
+
+/// One file:line:col: severity: message line per issue, without any banners
+pub fn print(reports: &[ScriptReport]) {
+    for issue in reports.iter().flat_map(|report| report.reported_issues()) {
+        println!("{}", issue);
+    }
+}
@@ -0,0 +1,116 @@
+use termcolor::Color;
+
+use super::{ScriptFailure, ScriptReport, Summary};
+use crate::console;
+use crate::thinbasic_script::Severity;
+
+pub fn print(reports: &[ScriptReport], failures: &[ScriptFailure]) {
+    for report in reports {
+        // With many scripts, only those with issues are worth showing
+        if reports.len() == 1 || report.reported_issues().next().is_some() {
+            print_script(report);
+        }
+    }
+
+    let summary = Summary::new(reports, failures);
+
+    print!("{}", "-".repeat(80));
+    print!("\nAnalysis finished: ");
+
+    if summary.issues == 0 {
+        console::print_color("no issues found", Color::Green);
+    } else {
+        console::print_color(&format!("{}", summary.issues), Color::Red);
+        console::print_color(" issue(s) found", Color::Red);
+    }
+
+    if summary.files > 1 {
+        let files_with_issues = reports
+            .iter()
+            .filter(|report| report.reported_issues().next().is_some())
+            .count();
+
+        print!(" in {} of {} files", files_with_issues, summary.files);
+    }
+
+    // Reasons were written to the error output already
+    if summary.failed > 0 {
+        print!(", ");
+        console::print_color(
+            &format!("{} file(s) could not be analysed", summary.failed),
+            Color::Red,
+        );
+    }
+
+    println!();
+
+    print!("{}", "-".repeat(80));
+    println!();
+}
+
+fn print_script(report: &ScriptReport) {
+    println!("{}", "-".repeat(80));
+    println!("{}", report.script_file);
+    println!("{}", "-".repeat(80));
+
+    for issue in report.reported_issues() {
+        print_location(
+            &report.source,
+            issue.line,
+            issue.pos,
+            issue.end_line,
+            issue.end_pos,
+        );
+        print!("{}", " ".repeat(13));
+        console::print_color(issue.severity.as_str(), severity_color(issue.severity));
+        print!(" {} ({}): ", issue.code, issue.rule_name);
+        console::print_color(&issue.summary, severity_color(issue.severity));
+        println!();
+
+        for related in &issue.related {
+            print_location(
+                &report.source,
+                related.line,
+                related.pos,
+                related.end_line,
+                related.end_pos,
+            );
+            print!("{}", " ".repeat(13));
+            console::print_color("note", Color::Cyan);
+            println!(": {}", related.message);
+        }
+
+        println!("{}", "-".repeat(80));
+    }
+    println!();
+}
+
+/// Source line with the given range underlined
+fn print_location(source: &str, line: u32, pos: u32, end_line: u32, end_pos: u32) {
+    print!("Line {:>5} - ", line);
+
+    console::print_color(
+        source.lines().nth((line - 1) as usize).unwrap_or(""),
+        Color::White,
+    );
+    println!();
+
+    // Whole range is underlined when it does not continue on the next lines
+    let width = if end_line == line && end_pos > pos {
+        end_pos - pos + 1
+    } else {
+        1
+    };
+
+    print!("{}", " ".repeat((pos + 12) as usize));
+    println!("{}", "^".repeat(width as usize));
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Error => Color::Red,
+        Severity::Warning => Color::Yellow,
+        Severity::Hint => Color::Cyan,
+        Severity::Info => Color::White,
+    }
+}
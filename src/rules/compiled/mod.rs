@@ -1,12 +1,37 @@
-use crate::thinbasic_script::{Code, IssueSummary};
-use crate::tokenizer;
-use crate::tokenizer::TokenType;
+use crate::rules;
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+use crate::tokenizer::{Directive, TokenCursor, TokenType};
 
-pub fn section_definition(code: &mut Code) -> Vec<IssueSummary> {
+pub struct SectionDefinition;
+
+impl Rule for SectionDefinition {
+    fn id(&self) -> &'static str {
+        "TC0002"
+    }
+
+    fn name(&self) -> &'static str {
+        "compiled-section"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "#COMPILED sections must not nest, must be closed by #ENDCOMPILED and use a valid LANGUAGE"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        section_definition(code)
+    }
+}
+
+fn section_definition(code: &mut Code) -> Vec<IssueSummary> {
     let mut issues_found: Vec<IssueSummary> = vec![];
 
     let tokens = code.get_tokens();
-    let mut token_iter = tokens.iter().peekable();
+    let mut cursor = TokenCursor::new(tokens);
 
     let mut in_compiled_block = false;
 
@@ -16,86 +41,73 @@ pub fn section_definition(code: &mut Code) -> Vec<IssueSummary> {
     let mut last_opened_compile_token_line = 0;
     let mut last_opened_compile_token_pos = 0;
 
-    let compiled_str = "#COMPILED".to_string();
-    let end_compiled_str = "#ENDCOMPILED".to_string();
-
     let file_name = &code.main_file_name[..];
 
-    while let Some(&token) = token_iter.peek() {
-        match &token.token_type {
-            TokenType::Symbol(kind) => {
-                token_iter.next();
-
-                if kind == &compiled_str {
-                    // Nesting check
-                    if !in_compiled_block {
-                        in_compiled_block = true;
-                        num_opened += 1;
-
-                        last_opened_compile_token_line = token.line;
-                        last_opened_compile_token_pos = token.pos;
-
-                        // Looking for parameters
-                        if tokenizer::parse_whitespace(&mut token_iter) {
-                            if tokenizer::parse_symbol(&mut token_iter, "SUPPRESSRTE") {
-                                tokenizer::parse_whitespace(&mut token_iter);
-                            }
+    while let Some(token) = cursor.next() {
+        if let TokenType::Directive(directive) = &token.token_type {
+            if *directive == Directive::Compiled {
+                // Nesting check
+                if !in_compiled_block {
+                    in_compiled_block = true;
+                    num_opened += 1;
+
+                    last_opened_compile_token_line = token.span.start.line;
+                    last_opened_compile_token_pos = token.span.start.column;
+
+                    // Looking for parameters
+                    if cursor.parse_whitespace() {
+                        if cursor.parse_identifier("SUPPRESSRTE") {
+                            cursor.parse_whitespace();
+                        }
 
-                            let lang_token = token_iter.peek().unwrap();
-                            let language_line = lang_token.line;
-                            let language_end_pos = lang_token.pos + 8;
-
-                            if tokenizer::parse_symbol(&mut token_iter, "LANGUAGE") {
-                                tokenizer::parse_whitespace(&mut token_iter);
-                                if !tokenizer::parse_equal_sign(&mut token_iter) {
-                                    issues_found.push(IssueSummary::new(
-                                        file_name,
-                                        language_line,
-                                        language_end_pos,
-                                        "#COMPILED LANGUAGE parameter must be followed by equal sign '='",
-                                    ));
-                                } else {
-                                    tokenizer::parse_whitespace(&mut token_iter);
-
-                                    if !tokenizer::parse_symbol(&mut token_iter, "FREEBASIC") {
-                                        let next_token = token_iter.peek().unwrap();
-                                        issues_found.push(IssueSummary::new(
-                                            file_name,
-                                            next_token.line,
-                                            next_token.pos,
-                                            "The only valid value for #COMPILED LANGUAGE parameter is FREEBASIC",
-                                        ));
-                                    }
+                        let language_position = cursor.position();
+                        let language_line = language_position.line;
+                        let language_end_pos = language_position.column + 8;
+
+                        if cursor.parse_identifier("LANGUAGE") {
+                            cursor.parse_whitespace();
+                            if !cursor.parse_equal_sign() {
+                                issues_found.push(IssueSummary::new(
+                                file_name,
+                                language_line,
+                                language_end_pos,
+                                "#COMPILED LANGUAGE parameter must be followed by equal sign '='",
+                            ));
+                            } else {
+                                cursor.parse_whitespace();
+
+                                if !cursor.parse_identifier("FREEBASIC") {
+                                    issues_found.push(rules::issue_at_next_token(
+                                    file_name,
+                                    &cursor,
+                                    "The only valid value for #COMPILED LANGUAGE parameter is FREEBASIC",
+                                ));
                                 }
                             }
                         }
-                    } else {
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            token.line,
-                            token.pos,
-                            "Nested #COMPILED not supported",
-                        ));
-                    }
-                }
-
-                if kind == &end_compiled_str {
-                    if !in_compiled_block {
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            token.line,
-                            token.pos,
-                            "#ENDCOMPILE without #COMPILED",
-                        ));
-                    } else {
-                        in_compiled_block = false;
-                        num_closed += 1;
                     }
+                } else {
+                    issues_found.push(IssueSummary::new(
+                        file_name,
+                        token.span.start.line,
+                        token.span.start.column,
+                        "Nested #COMPILED not supported",
+                    ));
                 }
             }
 
-            _ => {
-                token_iter.next();
+            if *directive == Directive::EndCompiled {
+                if !in_compiled_block {
+                    issues_found.push(IssueSummary::new(
+                        file_name,
+                        token.span.start.line,
+                        token.span.start.column,
+                        "#ENDCOMPILE without #COMPILED",
+                    ));
+                } else {
+                    in_compiled_block = false;
+                    num_closed += 1;
+                }
             }
         }
     }
@@ -1,150 +1,148 @@
-use crate::thinbasic_script::{Code, IssueSummary};
-use crate::tokenizer;
-use crate::tokenizer::TokenType;
+use crate::rules;
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+use crate::tokenizer::{Directive, Keyword, TokenCursor, TokenType};
 
-pub fn check(code: &mut Code) -> Vec<IssueSummary> {
+pub struct AliasSyntax;
+
+impl Rule for AliasSyntax {
+    fn id(&self) -> &'static str {
+        "TC0001"
+    }
+
+    fn name(&self) -> &'static str {
+        "alias-syntax"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "ALIAS must be followed by exactly one keyword, AS and a single new name"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        check(code)
+    }
+}
+
+fn check(code: &mut Code) -> Vec<IssueSummary> {
     let mut issues_found: Vec<IssueSummary> = vec![];
 
     let tokens = code.get_tokens();
-    let mut token_iter = tokens.iter().peekable();
-
-    let alias_str = "ALIAS".to_string();
+    let mut cursor = TokenCursor::new(tokens);
 
     let file_name = &code.main_file_name[..];
 
     let mut in_compiled = false;
-    let compiled_str = "#COMPILED".to_string();
-    let end_compiled_str = "#ENDCOMPILED".to_string();
 
-    while let Some(&token) = token_iter.peek() {
+    while let Some(token) = cursor.next() {
         match &token.token_type {
-            TokenType::Symbol(kind) => {
-                token_iter.next();
+            TokenType::Directive(Directive::Compiled) => {
+                in_compiled = true;
+                cursor.next();
+            }
+
+            TokenType::Directive(Directive::EndCompiled) => {
+                in_compiled = false;
+                cursor.next();
+            }
 
-                if kind == &compiled_str {
-                    in_compiled = true;
-                    token_iter.next();
+            TokenType::Keyword(Keyword::Alias) => {
+                if in_compiled {
+                    cursor.next();
                     continue;
                 }
 
-                if kind == &end_compiled_str {
-                    in_compiled = false;
-                    token_iter.next();
+                if !cursor.parse_whitespace() {
+                    issues_found.push(rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "ALIAS keyword must be followed by whitespace",
+                    ));
+
+                    cursor.next();
                     continue;
                 }
 
-                if kind == &alias_str {
-                    if in_compiled {
-                        token_iter.next();
-                        continue;
-                    }
-
-                    if !tokenizer::parse_whitespace(&mut token_iter) {
-                        issues_found.push(IssueSummary::new(
+                if !cursor.parse_any_word() {
+                    if !cursor.parse_any_text() {
+                        // This would indicate ALIAS in function
+                        issues_found.push(rules::issue_at_next_token(
                             file_name,
-                            token.line,
-                            token.pos + 5,
-                            "ALIAS keyword must be followed by whitespace",
+                            &cursor,
+                            "There must be exactly one keyword specified after ALIAS",
                         ));
-
-                        token_iter.next();
-                        continue;
                     }
 
-                    if !tokenizer::parse_any_symbol(&mut token_iter) {
-                        if !tokenizer::parse_any_text(&mut token_iter) {
-                            // This would indicate ALIAS in function
-                            let next_token = token_iter.peek().unwrap();
-
-                            issues_found.push(IssueSummary::new(
-                                file_name,
-                                next_token.line,
-                                next_token.pos,
-                                "There must be exactly one keyword specified after ALIAS",
-                            ));
-                        }
-
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
+                }
 
-                    let next_token = token_iter.peek().unwrap();
-                    let next_token_pos = next_token.pos - 1;
+                if !cursor.parse_whitespace() {
+                    let mut issue = rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "Aliased keyword must be followed by whitespace",
+                    );
 
-                    if !tokenizer::parse_whitespace(&mut token_iter) {
-                        let caused_by_eol = tokenizer::parse_end_of_line(&mut token_iter);
-                        let decr_line = if caused_by_eol { 2 } else { 0 };
+                    // Aliased keyword ends right where the next token starts
+                    issue.pos -= 1;
+                    issue.end_pos = issue.pos;
 
-                        let next_token = token_iter.peek().unwrap();
+                    issues_found.push(issue);
 
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            next_token.line - decr_line,
-                            next_token_pos,
-                            "Aliased keyword must be followed by whitespace",
-                        ));
-
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
+                }
 
-                    if !tokenizer::parse_symbol(&mut token_iter, "AS") {
-                        let next_token = token_iter.peek().unwrap();
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            next_token.line,
-                            next_token.pos,
-                            "Single keyword after ALIAS must be followed by AS",
-                        ));
+                if !cursor.parse_keyword(Keyword::As) {
+                    issues_found.push(rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "Single keyword after ALIAS must be followed by AS",
+                    ));
 
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
+                }
 
-                    if !tokenizer::parse_whitespace(&mut token_iter) {
-                        let next_token = token_iter.peek().unwrap();
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            next_token.line,
-                            next_token.pos,
-                            "AS must be followed by whitespace",
-                        ));
+                if !cursor.parse_whitespace() {
+                    issues_found.push(rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "AS must be followed by whitespace",
+                    ));
 
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
+                }
 
-                    if !tokenizer::parse_any_symbol(&mut token_iter) {
-                        let next_token = token_iter.peek().unwrap();
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            next_token.line,
-                            next_token.pos,
-                            "New name specified after AS must be a single word",
-                        ));
+                if !cursor.parse_any_word() {
+                    issues_found.push(rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "New name specified after AS must be a single word",
+                    ));
 
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
+                }
 
-                    if !tokenizer::is_last_on_line(&mut token_iter) {
-                        let next_token = token_iter.peek().unwrap();
-                        issues_found.push(IssueSummary::new(
-                            file_name,
-                            next_token.line,
-                            next_token.pos,
-                            "Only one word is allowed as alias after AS keyword",
-                        ));
+                if !cursor.is_last_on_line() {
+                    issues_found.push(rules::issue_at_next_token(
+                        file_name,
+                        &cursor,
+                        "Only one word is allowed as alias after AS keyword",
+                    ));
 
-                        token_iter.next();
-                        continue;
-                    }
+                    cursor.next();
+                    continue;
                 }
             }
 
-            _ => {
-                token_iter.next();
-            }
+            _ => {}
         }
     }
 
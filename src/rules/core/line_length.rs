@@ -0,0 +1,73 @@
+use crate::config::RuleConfig;
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+
+const DEFAULT_MAX_LENGTH: u32 = 120;
+
+pub struct LineLength {
+    max_length: u32,
+}
+
+impl Default for LineLength {
+    fn default() -> LineLength {
+        LineLength {
+            max_length: DEFAULT_MAX_LENGTH,
+        }
+    }
+}
+
+impl Rule for LineLength {
+    fn id(&self) -> &'static str {
+        "TC0003"
+    }
+
+    fn name(&self) -> &'static str {
+        "line-length"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Lines should not be longer than max-length characters (120 by default)"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), String> {
+        config.check_options(&["max-length"])?;
+
+        if let Some(max_length) = config.option_u32("max-length")? {
+            self.max_length = max_length;
+        }
+
+        Ok(())
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        let mut issues_found: Vec<IssueSummary> = vec![];
+
+        let file_name = code.main_file_name.clone();
+
+        for (index, line) in code.get_file_content().lines().enumerate() {
+            let length = line.chars().count() as u32;
+
+            if length > self.max_length {
+                issues_found.push(IssueSummary::new(
+                    &file_name,
+                    index as u32 + 1,
+                    self.max_length + 1,
+                    &format!(
+                        "Line is {} characters long, maximum allowed is {}",
+                        length, self.max_length
+                    ),
+                ));
+            }
+        }
+
+        issues_found
+    }
+}
@@ -1 +1,7 @@
 pub mod alias;
+pub mod line_length;
+pub mod number_literal;
+pub mod syntax;
+pub mod undeclared;
+pub mod unterminated_literal;
+pub mod unused_suppression;
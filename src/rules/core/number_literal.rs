@@ -0,0 +1,44 @@
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+use crate::tokenizer::TokenType;
+
+pub struct NumberLiteral;
+
+impl Rule for NumberLiteral {
+    fn id(&self) -> &'static str {
+        "TC0005"
+    }
+
+    fn name(&self) -> &'static str {
+        "number-literal"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Number literals must be valid decimal, &H hexadecimal, &B binary or &O octal numbers"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        let mut issues_found: Vec<IssueSummary> = vec![];
+
+        let tokens = code.get_tokens();
+        let file_name = &code.main_file_name[..];
+
+        for token in tokens {
+            if let TokenType::Number(literal) = &token.token_type {
+                if let Err(reason) = &literal.value {
+                    issues_found.push(IssueSummary::from_span(
+                        file_name,
+                        &token.span,
+                        &format!("Malformed number literal {}, {}", token.text, reason),
+                    ));
+                }
+            }
+        }
+
+        issues_found
+    }
+}
@@ -0,0 +1,40 @@
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+
+pub struct ScriptSyntax;
+
+impl Rule for ScriptSyntax {
+    fn id(&self) -> &'static str {
+        "TC0007"
+    }
+
+    fn name(&self) -> &'static str {
+        "syntax-error"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Statements must follow the thinBasic grammar"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        let file_name = code.main_file_name.clone();
+
+        code.get_syntax_tree()
+            .1
+            .iter()
+            .map(|error| {
+                let mut issue = IssueSummary::from_span(&file_name, &error.span, &error.message);
+
+                if let Some((span, message)) = &error.related {
+                    issue.add_related(span, message);
+                }
+
+                issue
+            })
+            .collect()
+    }
+}
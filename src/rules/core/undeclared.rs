@@ -0,0 +1,654 @@
+use crate::parser::ast::{
+    CaseItem, Expression, ExpressionKind, Name, Script, Statement, StatementKind,
+};
+use crate::rules::Rule;
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+use crate::tokenizer::{Directive, Keyword};
+
+/// Core functions, which are available without USES and may look like arrays or variables
+const CORE_FUNCTIONS: &[&str] = &[
+    "ABS",
+    "ACOS",
+    "ASC",
+    "ASIN",
+    "ATN",
+    "BIN$",
+    "CEIL",
+    "CHR$",
+    "CODEPTR",
+    "COMMAND$",
+    "COS",
+    "CURDIR$",
+    "CVD",
+    "CVI",
+    "CVL",
+    "CVS",
+    "DATE$",
+    "DEG2RAD",
+    "DIR$",
+    "DOEVENTS",
+    "ENVIRON$",
+    "ERL",
+    "ERR",
+    "EXP",
+    "EXP10",
+    "EXP2",
+    "EXTRACT$",
+    "FIX",
+    "FLOOR",
+    "FORMAT$",
+    "FRAC",
+    "GRAB$",
+    "HEX$",
+    "HI",
+    "IIF",
+    "IIF$",
+    "INPUTBOX$",
+    "INSTR",
+    "INT",
+    "ISFALSE",
+    "ISTRUE",
+    "JOIN$",
+    "LBOUND",
+    "LCASE$",
+    "LEFT$",
+    "LEN",
+    "LO",
+    "LOG",
+    "LOG10",
+    "LOG2",
+    "LTRIM$",
+    "MAKDWD",
+    "MAKINT",
+    "MAKLNG",
+    "MAKWRD",
+    "MAX",
+    "MCASE$",
+    "MID$",
+    "MIN",
+    "MKD$",
+    "MKI$",
+    "MKL$",
+    "MKS$",
+    "MSGBOX",
+    "OCT$",
+    "PARSE$",
+    "PARSECOUNT",
+    "PEEK",
+    "POKE",
+    "RAD2DEG",
+    "REMAIN$",
+    "REMOVE$",
+    "REPEAT$",
+    "REPLACE$",
+    "RETAIN$",
+    "RIGHT$",
+    "RND",
+    "ROUND",
+    "RTRIM$",
+    "SGN",
+    "SHRINK$",
+    "SIN",
+    "SIZEOF",
+    "SLEEP",
+    "SPACE$",
+    "SQR",
+    "STR$",
+    "STRING$",
+    "STRPTR",
+    "STRREVERSE$",
+    "TALLY",
+    "TAN",
+    "TIME$",
+    "TIMER",
+    "TRIM$",
+    "UBOUND",
+    "UCASE$",
+    "USING$",
+    "VAL",
+    "VARPTR",
+    "WRAP$",
+];
+
+/// Values describing the event inside CALLBACK FUNCTION
+const CALLBACK_NAMES: &[&str] = &[
+    "CBCTL", "CBCTLMSG", "CBHNDL", "CBLPARAM", "CBMSG", "CBNMCODE", "CBNMHDR", "CBNMHWND",
+    "CBNMID", "CBWPARAM",
+];
+
+/// Prefix of core functions, which are available without USES
+const CORE_PREFIX: &str = "APP_";
+
+pub struct UndeclaredVariable;
+
+impl Rule for UndeclaredVariable {
+    fn id(&self) -> &'static str {
+        "TC0008"
+    }
+
+    fn name(&self) -> &'static str {
+        "undeclared-variable"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables must be declared by DIM, LOCAL, GLOBAL or as parameters before use, members by their TYPE"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        let (script, _) = code.get_syntax_tree();
+
+        let mut checker = Checker::new(&code.main_file_name, script);
+        checker.check_statements(&script.statements);
+
+        checker.issues
+    }
+}
+
+struct Checker {
+    file_name: String,
+    table: SymbolTable,
+
+    /// Modules loaded by USES, whose functions start by the module name
+    modules: Vec<String>,
+
+    /// Names introduced by ALIAS, which stand for keywords
+    aliases: Vec<String>,
+
+    /// Script includes other files, whose routines are not known
+    includes: bool,
+
+    issues: Vec<IssueSummary>,
+}
+
+impl Checker {
+    fn new(file_name: &str, script: &Script) -> Checker {
+        let mut checker = Checker {
+            file_name: file_name.to_string(),
+            table: SymbolTable::new(script),
+            modules: vec![],
+            aliases: vec![],
+            includes: false,
+            issues: vec![],
+        };
+
+        checker.collect_exclusions(&script.statements);
+
+        checker
+    }
+
+    fn collect_exclusions(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            match &statement.kind {
+                StatementKind::Call { target, arguments } if is_name(target, "USES") => {
+                    for argument in arguments {
+                        if let ExpressionKind::Text(text) = &argument.kind {
+                            self.modules.push(text.trim_matches('"').to_uppercase());
+                        }
+                    }
+                }
+                StatementKind::Alias {
+                    alias: Some(alias), ..
+                } => self.aliases.push(alias.name.clone()),
+                StatementKind::Directive {
+                    directive: Directive::Include,
+                    ..
+                } => self.includes = true,
+                _ => {}
+            }
+        }
+    }
+
+    fn check_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Assignment { target, value, .. } => {
+                self.check_target(target);
+                self.check_expression(value);
+            }
+            // Procedure itself is not a variable, only what it gets
+            StatementKind::Call { target, arguments } => {
+                if let ExpressionKind::Member {
+                    object: Some(object),
+                    ..
+                } = &target.kind
+                {
+                    self.check_target(object);
+                }
+
+                self.check_expressions(arguments);
+            }
+            StatementKind::Declaration { variables, .. } => {
+                for variable in variables {
+                    for dimension in &variable.dimensions {
+                        if let Some(lower) = &dimension.lower {
+                            self.check_expression(lower);
+                        }
+
+                        self.check_expression(&dimension.upper);
+                    }
+
+                    if let Some(at) = &variable.at {
+                        self.check_expression(at);
+                    }
+
+                    if let Some(value) = &variable.value {
+                        self.check_expression(value);
+                    }
+                }
+            }
+            StatementKind::Equate { value, .. } => self.check_expression(value),
+            StatementKind::If {
+                branches,
+                else_body,
+            } => {
+                for branch in branches {
+                    self.check_expression(&branch.condition);
+                    self.check_statements(&branch.body);
+                }
+
+                if let Some(body) = else_body {
+                    self.check_statements(body);
+                }
+            }
+            StatementKind::For {
+                variable,
+                from,
+                to,
+                step,
+                body,
+            } => {
+                self.check_name(variable);
+                self.check_expression(from);
+                self.check_expression(to);
+
+                if let Some(step) = step {
+                    self.check_expression(step);
+                }
+
+                self.check_statements(body);
+            }
+            StatementKind::ForEach {
+                variable,
+                collection,
+                body,
+            } => {
+                self.check_name(variable);
+                self.check_expression(collection);
+                self.check_statements(body);
+            }
+            StatementKind::While { condition, body } => {
+                self.check_expression(condition);
+                self.check_statements(body);
+            }
+            StatementKind::Do {
+                condition,
+                body,
+                loop_condition,
+            } => {
+                for condition in condition.iter().chain(loop_condition.iter()) {
+                    self.check_expression(&condition.condition);
+                }
+
+                self.check_statements(body);
+            }
+            StatementKind::Select {
+                subject,
+                cases,
+                else_body,
+            } => {
+                self.check_expression(subject);
+
+                for case in cases {
+                    for item in &case.items {
+                        match item {
+                            CaseItem::Value(value) | CaseItem::Comparison(_, value) => {
+                                self.check_expression(value)
+                            }
+                            CaseItem::Range(from, to) => {
+                                self.check_expression(from);
+                                self.check_expression(to);
+                            }
+                        }
+                    }
+
+                    self.check_statements(&case.body);
+                }
+
+                if let Some(body) = else_body {
+                    self.check_statements(body);
+                }
+            }
+            StatementKind::With { object, body } => {
+                self.check_target(object);
+                self.check_statements(body);
+            }
+            StatementKind::Routine(routine) => {
+                for parameter in &routine.parameters {
+                    if let Some(default) = &parameter.default {
+                        self.check_expression(default);
+                    }
+                }
+
+                self.check_statements(&routine.body);
+            }
+            StatementKind::ConstBlock { body } | StatementKind::Error { body } => {
+                self.check_statements(body)
+            }
+            StatementKind::Return(Some(value)) => self.check_expression(value),
+            _ => {}
+        }
+    }
+
+    fn check_expressions(&mut self, expressions: &[Expression]) {
+        for expression in expressions {
+            self.check_expression(expression);
+        }
+    }
+
+    /// Value which is read, calls of routines and core functions pass as declared,
+    /// so unknown call targets are reported as the arrays they most likely are
+    fn check_expression(&mut self, expression: &Expression) {
+        match &expression.kind {
+            ExpressionKind::Name(name) => self.check_name(name),
+            ExpressionKind::Call { target, arguments } => {
+                // Routines of included files cannot be told from arrays
+                if !(self.includes && matches!(target.kind, ExpressionKind::Name(_))) {
+                    self.check_expression(target);
+                }
+
+                self.check_expressions(arguments);
+            }
+            ExpressionKind::Member {
+                object: Some(object),
+                member,
+            } => self.check_member(object, member),
+            ExpressionKind::Unary { operand, .. } => self.check_expression(operand),
+            ExpressionKind::Binary { left, right, .. } => {
+                self.check_expression(left);
+                self.check_expression(right);
+            }
+            _ => {}
+        }
+    }
+
+    /// Variable which is assigned or whose member is used, where calls can be only arrays
+    fn check_target(&mut self, target: &Expression) {
+        match &target.kind {
+            ExpressionKind::Name(name) => self.check_name(name),
+            ExpressionKind::Call { target, arguments } => {
+                self.check_target(target);
+                self.check_expressions(arguments);
+            }
+            ExpressionKind::Member {
+                object: Some(object),
+                member,
+            } => self.check_member(object, member),
+            _ => self.check_expression(target),
+        }
+    }
+
+    /// Variable of TYPE defined in the script can use only the members the TYPE declares
+    fn check_member(&mut self, object: &Expression, member: &Name) {
+        self.check_target(object);
+
+        // Variable itself or element of array, such as points(1).x
+        let variable = match &object.kind {
+            ExpressionKind::Name(name) => name,
+            ExpressionKind::Call { target, .. } => match &target.kind {
+                ExpressionKind::Name(name) => name,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let scope = self.table.scope_at(variable.span.start);
+
+        let type_name = match self.table.resolve(scope, &variable.name) {
+            Some(symbol) if matches!(symbol.kind, SymbolKind::Variable | SymbolKind::Parameter) => {
+                match &symbol.data_type {
+                    Some(data_type) => data_type.clone(),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        let members = match self.table.members(&type_name) {
+            Some(members) => members,
+            None => return,
+        };
+
+        if members.symbol(&member.name).is_none() {
+            let type_text = self
+                .table
+                .resolve(self.table.script_scope(), &type_name)
+                .map_or(type_name.as_str(), |symbol| symbol.text.as_str());
+
+            let summary = format!(
+                "Member '{}' is not declared in TYPE {}",
+                member.text, type_text
+            );
+
+            self.issues.push(IssueSummary::from_span(
+                &self.file_name,
+                &member.span,
+                &summary,
+            ));
+        }
+    }
+
+    fn check_name(&mut self, name: &Name) {
+        if self.is_excluded(&name.name) {
+            return;
+        }
+
+        let scope = self.table.scope_at(name.span.start);
+
+        if self.table.resolve(scope, &name.name).is_some() {
+            return;
+        }
+
+        let mut summary = format!("Variable '{}' is not declared", name.text);
+
+        // Closest name wins, the innermost one when more are equally close
+        let suggestion = self
+            .table
+            .visible_symbols(scope)
+            .into_iter()
+            .filter(|symbol| {
+                matches!(
+                    symbol.kind,
+                    SymbolKind::Variable | SymbolKind::Parameter | SymbolKind::Routine
+                )
+            })
+            .map(|symbol| (edit_distance(&name.name, &symbol.name), symbol))
+            .filter(|(distance, _)| *distance <= max_typo_distance(&name.name))
+            .min_by_key(|(distance, _)| *distance);
+
+        if let Some((_, symbol)) = suggestion {
+            summary.push_str(&format!(", did you mean '{}'?", symbol.text));
+        }
+
+        self.issues.push(IssueSummary::from_span(
+            &self.file_name,
+            &name.span,
+            &summary,
+        ));
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        Keyword::from_name(name).is_some()
+            || self.aliases.iter().any(|alias| alias == name)
+            || CORE_FUNCTIONS.contains(&name)
+            || CALLBACK_NAMES.contains(&name)
+            || name.starts_with(CORE_PREFIX)
+            || self
+                .modules
+                .iter()
+                .any(|module| name.starts_with(module) && name[module.len()..].starts_with('_'))
+    }
+}
+
+fn is_name(expression: &Expression, wanted: &str) -> bool {
+    matches!(&expression.kind, ExpressionKind::Name(name) if name.name == wanted)
+}
+
+/// Longer names are allowed more typos, while names of one or two characters would
+/// match almost anything, so they get no suggestions
+fn max_typo_distance(name: &str) -> usize {
+    match name.chars().count() {
+        0..=2 => 0,
+        length => (length / 3).max(1),
+    }
+}
+
+/// Number of inserted, deleted or replaced characters turning one text into another
+fn edit_distance(from: &str, to: &str) -> usize {
+    let to: Vec<char> = to.chars().collect();
+    let mut previous: Vec<usize> = (0..=to.len()).collect();
+
+    for (i, from_char) in from.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, to_char) in to.iter().enumerate() {
+            let replaced = previous[j] + usize::from(from_char != *to_char);
+            current.push(replaced.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[to.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, Checker};
+    use crate::parser::parse;
+    use crate::tokenizer::get_tokens;
+
+    fn check(code: &str) -> Vec<String> {
+        let (script, _) = parse(get_tokens(code));
+
+        let mut checker = Checker::new("test.tbasic", &script);
+        checker.check_statements(&script.statements);
+
+        checker
+            .issues
+            .iter()
+            .map(|issue| format!("{}:{} {}", issue.line, issue.pos, issue.summary))
+            .collect()
+    }
+
+    #[test]
+    fn distance_counts_edits() {
+        assert_eq!(edit_distance("COUNTER", "COUNTER"), 0);
+        assert_eq!(edit_distance("CONTER", "COUNTER"), 1);
+        assert_eq!(edit_distance("KITTEN", "SITTING"), 3);
+        assert_eq!(edit_distance("", "ABC"), 3);
+    }
+
+    #[test]
+    fn undeclared_variables_are_reported() {
+        let issues = check(
+            "Uses \"Console\"\n\
+             Global counter As Long\n\
+             Function Total(ByVal items As Long) As Long\n\
+               Local sum As Long\n\
+               For i = 1 To itens\n\
+                 sum += Console_ReadLine() + Len(conter)\n\
+               Next\n\
+               Function = sum + Total(1) + Timer\n\
+             End Function\n\
+             x = Console_GetKey\n\
+             PrintL sum\n\
+             Dim arr(10) As Long\n\
+             arr(1) = Total(arrr(2)) + Str$(arr(3))\n",
+        );
+
+        assert_eq!(
+            issues,
+            vec![
+                "5:5 Variable 'i' is not declared",
+                "5:14 Variable 'itens' is not declared, did you mean 'items'?",
+                "6:33 Variable 'conter' is not declared, did you mean 'counter'?",
+                "10:1 Variable 'x' is not declared",
+                "11:8 Variable 'sum' is not declared",
+                "13:16 Variable 'arrr' is not declared, did you mean 'arr'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn included_routines_and_core_names_pass() {
+        let issues = check(
+            "#Include \"lib.tbasicinc\"\n\
+             Dim r As Long = Helper(1) + Val(\"3\")\n\
+             Callback Function OnEvent() As Long\n\
+               If CBMSG = 1 Then Function = CBCTL\n\
+             End Function\n",
+        );
+
+        assert_eq!(issues, Vec::<String>::new());
+
+        let issues = check("Dim r As Long = Helper(1)\n");
+        assert_eq!(issues, vec!["1:17 Variable 'Helper' is not declared"]);
+    }
+
+    #[test]
+    fn short_names_get_no_suggestion() {
+        let issues = check(
+            "Dim a, q, sum As Long\n\
+             For i = 1 To 2\n\
+               y = z + sun\n\
+             Next\n",
+        );
+
+        assert_eq!(
+            issues,
+            vec![
+                "2:5 Variable 'i' is not declared",
+                "3:1 Variable 'y' is not declared",
+                "3:5 Variable 'z' is not declared",
+                "3:9 Variable 'sun' is not declared, did you mean 'sum'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn members_of_script_types_are_checked() {
+        let issues = check(
+            "Type Point\n\
+               x As Long\n\
+               y As Long\n\
+             End Type\n\
+             Dim p As Point\n\
+             Dim points(3) As Point\n\
+             Dim title As String\n\
+             p.x = points(1).y + p.z + points(2).w\n\
+             title.anything = 1\n",
+        );
+
+        assert_eq!(
+            issues,
+            vec![
+                "8:23 Member 'z' is not declared in TYPE Point",
+                "8:37 Member 'w' is not declared in TYPE Point",
+            ]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+
+pub struct UnterminatedLiteral;
+
+impl Rule for UnterminatedLiteral {
+    fn id(&self) -> &'static str {
+        "TC0006"
+    }
+
+    fn name(&self) -> &'static str {
+        "unterminated-literal"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn description(&self) -> &'static str {
+        "Strings must be closed by quote on the same line and block comments by */"
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary> {
+        let file_name = code.main_file_name.clone();
+
+        code.get_token_errors()
+            .iter()
+            .map(|error| IssueSummary::from_span(&file_name, &error.span, &error.message))
+            .collect()
+    }
+}
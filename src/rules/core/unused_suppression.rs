@@ -0,0 +1,28 @@
+use crate::rules::Rule;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+
+/// Suppression comments are known to be unused only once all the other rules ran,
+/// so the issues are reported when suppressions are applied, not by the check
+pub struct UnusedSuppression;
+
+impl Rule for UnusedSuppression {
+    fn id(&self) -> &'static str {
+        "TC0004"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-suppression"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "Suppression comments should match an issue, stale ones are to be removed"
+    }
+
+    fn check(&self, _code: &mut Code) -> Vec<IssueSummary> {
+        vec![]
+    }
+}
@@ -1,2 +1,83 @@
 pub mod compiled;
 pub mod core;
+
+use crate::config::RuleConfig;
+use crate::thinbasic_script::{Code, IssueSummary, Severity};
+use crate::tokenizer::TokenCursor;
+
+/// Single check performed over the script
+pub trait Rule {
+    /// Stable identifier, such as TC0001
+    fn id(&self) -> &'static str;
+
+    /// Short human readable name, such as alias-syntax
+    fn name(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity;
+
+    /// One sentence explaining what the rule checks
+    fn description(&self) -> &'static str;
+
+    /// Rules which are too opinionated for every script need to be enabled explicitly
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Applies the rule specific options from the configuration file
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), String> {
+        config.check_options(&[])
+    }
+
+    fn check(&self, code: &mut Code) -> Vec<IssueSummary>;
+}
+
+/// Rule enabled for the analysis, with severity resolved from the configuration
+pub struct ActiveRule {
+    pub rule: Box<dyn Rule>,
+    pub severity: Severity,
+}
+
+/// Lists every rule known to thinClippy, in the order of their identifiers
+pub fn registry() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(self::core::alias::AliasSyntax),
+        Box::new(self::compiled::SectionDefinition),
+        Box::new(self::core::line_length::LineLength::default()),
+        Box::new(self::core::unused_suppression::UnusedSuppression),
+        Box::new(self::core::number_literal::NumberLiteral),
+        Box::new(self::core::unterminated_literal::UnterminatedLiteral),
+        Box::new(self::core::syntax::ScriptSyntax),
+        Box::new(self::core::undeclared::UndeclaredVariable),
+    ]
+}
+
+/// Issue at the token the cursor points to, which may be missing at the end of file
+pub fn issue_at_next_token(file_name: &str, cursor: &TokenCursor, summary: &str) -> IssueSummary {
+    let position = cursor.position();
+
+    if cursor.peek().is_some() {
+        IssueSummary::new(file_name, position.line, position.column, summary)
+    } else {
+        let summary = format!("Unexpected end of file: {}", summary);
+        IssueSummary::new(file_name, position.line, position.column, &summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::registry;
+
+    #[test]
+    fn rule_ids_are_unique() {
+        let rules = registry();
+
+        for (index, rule) in rules.iter().enumerate() {
+            assert!(rules[index + 1..]
+                .iter()
+                .all(|other| other.id() != rule.id()));
+            assert!(rules[index + 1..]
+                .iter()
+                .all(|other| other.name() != rule.name()));
+        }
+    }
+}
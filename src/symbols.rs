@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+
+use crate::parser::ast::{DataType, Name, Script, Statement, StatementKind};
+use crate::tokenizer::{Keyword, Position, Span};
+
+/// What the declared name stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    /// %NUMERIC or $STRING equate
+    Equate,
+    /// User defined TYPE
+    Type,
+    /// Member of TYPE
+    Member,
+    /// FUNCTION or SUB defined in the script
+    Routine,
+    /// FUNCTION or SUB DECLAREd in external library
+    External,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    /// Name in uppercase, as thinBasic is not case sensitive
+    pub name: String,
+    /// Spelling used by the declaration
+    pub text: String,
+    pub kind: SymbolKind,
+    /// Declared type in uppercase, such as LONG or name of TYPE, return type of routines
+    pub data_type: Option<String>,
+    /// Name in the declaration
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeId(usize);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScopeKind {
+    /// Globals, equates, types and routines, visible everywhere
+    Script,
+    /// Parameters and locals of FUNCTION or SUB of the given name
+    Routine(String),
+    /// Members of TYPE of the given name
+    Type(String),
+}
+
+#[derive(Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// Scope whose symbols are visible too, none for the script and types
+    pub parent: Option<ScopeId>,
+    /// Code the scope covers, none for the script, which covers everything
+    pub span: Option<Span>,
+    /// Symbols in the order of their declarations
+    pub symbols: Vec<Symbol>,
+    /// Position of the first declaration of each name in symbols
+    index: HashMap<String, usize>,
+}
+
+impl Scope {
+    fn new(kind: ScopeKind, parent: Option<ScopeId>, span: Option<Span>) -> Scope {
+        Scope {
+            kind,
+            parent,
+            span,
+            symbols: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Symbol declared directly in this scope, the first declaration wins
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.index.get(name).map(|&index| &self.symbols[index])
+    }
+}
+
+/// Names declared in the script, grouped by the scope they are visible in
+///
+/// Declarations are found regardless of their order, the same way thinBasic sees
+/// routines and globals before the script runs.
+#[derive(Debug)]
+pub struct SymbolTable {
+    scopes: Vec<Scope>,
+    /// Routine scopes ordered by their start, routines cannot be nested
+    routines: Vec<ScopeId>,
+}
+
+impl SymbolTable {
+    pub fn new(script: &Script) -> SymbolTable {
+        let mut table = SymbolTable {
+            scopes: vec![Scope::new(ScopeKind::Script, None, None)],
+            routines: vec![],
+        };
+
+        table.declare(&script.statements, table.script_scope());
+        let scopes = &table.scopes;
+        table
+            .routines
+            .sort_by_key(|id| scopes[id.0].span.map(|span| span.start));
+
+        table
+    }
+
+    pub fn script_scope(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// Routine containing the position, or the script scope outside of routines
+    ///
+    /// Types have no parent, so names inside them are looked up in the script scope.
+    pub fn scope_at(&self, position: Position) -> ScopeId {
+        let starts_before = |id: &ScopeId| {
+            self.scope(*id)
+                .span
+                .is_some_and(|span| span.start <= position)
+        };
+        let after = self.routines.partition_point(starts_before);
+
+        match after.checked_sub(1).map(|index| self.routines[index]) {
+            Some(id) if self.scope(id).span.is_some_and(|span| position < span.end) => id,
+            _ => self.script_scope(),
+        }
+    }
+
+    /// Symbol the name refers to in the scope, the innermost declaration wins
+    pub fn resolve(&self, scope: ScopeId, name: &str) -> Option<&Symbol> {
+        let name = name.to_uppercase();
+        let mut current = Some(scope);
+
+        while let Some(id) = current {
+            let scope = self.scope(id);
+
+            if let Some(symbol) = scope.symbol(&name) {
+                return Some(symbol);
+            }
+
+            current = scope.parent;
+        }
+
+        None
+    }
+
+    /// All the symbols visible in the scope, starting by the innermost ones
+    pub fn visible_symbols(&self, scope: ScopeId) -> Vec<&Symbol> {
+        let mut symbols = vec![];
+        let mut current = Some(scope);
+
+        while let Some(id) = current {
+            let scope = self.scope(id);
+
+            symbols.extend(scope.symbols.iter());
+            current = scope.parent;
+        }
+
+        symbols
+    }
+
+    /// Scope with members of TYPE of the given name
+    pub fn members(&self, type_name: &str) -> Option<&Scope> {
+        let type_symbol = self
+            .scope(self.script_scope())
+            .symbol(&type_name.to_uppercase())?;
+
+        if type_symbol.kind != SymbolKind::Type {
+            return None;
+        }
+
+        self.scopes
+            .iter()
+            .find(|scope| scope.kind == ScopeKind::Type(type_symbol.name.clone()))
+    }
+
+    fn add_scope(&mut self, kind: ScopeKind, parent: Option<ScopeId>, span: Span) -> ScopeId {
+        let id = ScopeId(self.scopes.len());
+
+        if let ScopeKind::Routine(_) = kind {
+            self.routines.push(id);
+        }
+
+        self.scopes.push(Scope::new(kind, parent, Some(span)));
+
+        id
+    }
+
+    fn add_symbol(
+        &mut self,
+        scope: ScopeId,
+        name: &Name,
+        kind: SymbolKind,
+        data_type: Option<&DataType>,
+    ) {
+        let scope = &mut self.scopes[scope.0];
+
+        scope
+            .index
+            .entry(name.name.clone())
+            .or_insert(scope.symbols.len());
+
+        scope.symbols.push(Symbol {
+            name: name.name.clone(),
+            text: name.text.clone(),
+            kind,
+            data_type: data_type.map(|data_type| data_type.name.name.clone()),
+            span: name.span,
+        });
+    }
+
+    /// Collects declarations of the statements, including those nested in blocks
+    fn declare(&mut self, statements: &[Statement], scope: ScopeId) {
+        let script = self.script_scope();
+
+        for statement in statements {
+            match &statement.kind {
+                StatementKind::Declaration { keyword, variables } => {
+                    let target = if *keyword == Keyword::Global {
+                        script
+                    } else {
+                        scope
+                    };
+
+                    for variable in variables {
+                        self.add_symbol(
+                            target,
+                            &variable.name,
+                            SymbolKind::Variable,
+                            variable.data_type.as_ref(),
+                        );
+                    }
+                }
+                // Equates are global wherever they are defined
+                StatementKind::Equate { name, .. } => {
+                    self.add_symbol(script, name, SymbolKind::Equate, None)
+                }
+                StatementKind::Routine(routine) => {
+                    self.add_symbol(
+                        script,
+                        &routine.name,
+                        SymbolKind::Routine,
+                        routine.return_type.as_ref(),
+                    );
+
+                    let routine_scope = self.add_scope(
+                        ScopeKind::Routine(routine.name.name.clone()),
+                        Some(script),
+                        statement.span,
+                    );
+
+                    for parameter in &routine.parameters {
+                        self.add_symbol(
+                            routine_scope,
+                            &parameter.name,
+                            SymbolKind::Parameter,
+                            parameter.data_type.as_ref(),
+                        );
+                    }
+
+                    self.declare(&routine.body, routine_scope);
+                }
+                StatementKind::Declare(declare) => self.add_symbol(
+                    script,
+                    &declare.name,
+                    SymbolKind::External,
+                    declare.return_type.as_ref(),
+                ),
+                StatementKind::Type { name, members } => {
+                    self.add_symbol(script, name, SymbolKind::Type, None);
+
+                    let type_scope =
+                        self.add_scope(ScopeKind::Type(name.name.clone()), None, statement.span);
+
+                    for member in members {
+                        self.add_symbol(
+                            type_scope,
+                            &member.name,
+                            SymbolKind::Member,
+                            member.data_type.as_ref(),
+                        );
+                    }
+                }
+                StatementKind::ConstBlock { body }
+                | StatementKind::While { body, .. }
+                | StatementKind::Do { body, .. }
+                | StatementKind::For { body, .. }
+                | StatementKind::ForEach { body, .. }
+                | StatementKind::With { body, .. }
+                | StatementKind::Error { body } => self.declare(body, scope),
+                StatementKind::If {
+                    branches,
+                    else_body,
+                } => {
+                    for branch in branches {
+                        self.declare(&branch.body, scope);
+                    }
+
+                    if let Some(body) = else_body {
+                        self.declare(body, scope);
+                    }
+                }
+                StatementKind::Select {
+                    cases, else_body, ..
+                } => {
+                    for case in cases {
+                        self.declare(&case.body, scope);
+                    }
+
+                    if let Some(body) = else_body {
+                        self.declare(body, scope);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScopeKind, SymbolKind, SymbolTable};
+    use crate::parser::parse;
+    use crate::tokenizer::{get_tokens, Position};
+
+    const SCRIPT: &str = "\
+Global counter As Long
+Dim title As String
+%LIMIT = 10
+Begin Const
+  $NAME = \"demo\"
+End Const
+Type Point
+  x As Long
+  y As Long
+End Type
+Declare Function Beep Lib \"kernel32\" (ByVal f As Dword, ByVal d As Dword) As Long
+
+Function Area(ByVal width As Double, height As Double) As Double
+  Local result As Double
+  If width > 0 Then
+    Dim p As Point
+    Global shared As Long
+  End If
+  Function = result
+End Function
+
+Sub Main()
+  Static calls As Long
+End Sub
+";
+
+    #[test]
+    fn declarations_land_in_scopes() {
+        let table = SymbolTable::new(&parse(get_tokens(SCRIPT)).0);
+        let script = table.script_scope();
+
+        let names: Vec<&str> = table
+            .scope(script)
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["COUNTER", "TITLE", "%LIMIT", "$NAME", "POINT", "BEEP", "AREA", "SHARED", "MAIN"]
+        );
+
+        let members = &table.members("point").unwrap().symbols;
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].kind, SymbolKind::Member);
+        assert_eq!(members[1].data_type.as_deref(), Some("LONG"));
+
+        assert_eq!(
+            table.resolve(script, "beep").unwrap().kind,
+            SymbolKind::External
+        );
+        assert!(table.resolve(script, "result").is_none());
+    }
+
+    #[test]
+    fn routine_scope_sees_locals_and_globals() {
+        let table = SymbolTable::new(&parse(get_tokens(SCRIPT)).0);
+
+        // Inside the IF in Area
+        let scope = table.scope_at(Position {
+            offset: SCRIPT.find("Dim p").unwrap(),
+            line: 16,
+            column: 5,
+        });
+
+        assert_eq!(
+            table.scope(scope).kind,
+            ScopeKind::Routine("AREA".to_string())
+        );
+
+        let width = table.resolve(scope, "Width").unwrap();
+        assert_eq!(width.kind, SymbolKind::Parameter);
+        assert_eq!(width.text, "width");
+
+        assert_eq!(
+            table.resolve(scope, "p").unwrap().data_type.as_deref(),
+            Some("POINT")
+        );
+        assert_eq!(
+            table.resolve(scope, "counter").unwrap().kind,
+            SymbolKind::Variable
+        );
+        assert!(table.resolve(scope, "calls").is_none());
+
+        let outside = table.scope_at(Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        });
+        assert_eq!(outside, table.script_scope());
+
+        // Inside the TYPE block, where member types and sizes may use equates
+        let in_type = table.scope_at(Position {
+            offset: SCRIPT.find("x As Long").unwrap(),
+            line: 8,
+            column: 3,
+        });
+        assert_eq!(in_type, table.script_scope());
+        assert!(table.resolve(in_type, "%limit").is_some());
+    }
+}
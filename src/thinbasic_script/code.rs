@@ -1,40 +1,99 @@
+use super::{Encoding, LoadError};
+use crate::parser;
+use crate::parser::ast::Script;
+use crate::parser::SyntaxError;
 use crate::tokenizer;
-use crate::tokenizer::TokenInfo;
+use crate::tokenizer::{TokenError, TokenInfo};
+use std::cell::OnceCell;
 use std::fs;
+use std::path::Path;
 
 pub struct Code {
     pub main_file_name: String,
 
+    /// Encoding detected when loading, to be used when writing the script back
+    pub encoding: Encoding,
+
+    /// Content decoded once when loading, the file is not read again
     file_content: String,
+
+    // Created on first request and shared by all the rules
+    tokens: OnceCell<(Vec<TokenInfo>, Vec<TokenError>)>,
+    syntax_tree: OnceCell<(Script, Vec<SyntaxError>)>,
 }
 
 impl Code {
-    pub fn new(main_file_name: &str) -> Result<Code, &'static str> {
-        let file_content = match fs::read_to_string(&main_file_name) {
-            Ok(content) => content.to_uppercase(),
-            Err(_) => return Err("Could not load script file contents"),
-        };
+    pub fn new(main_file_name: &str) -> Result<Code, LoadError> {
+        let (file_content, encoding) = load(main_file_name)?;
 
         Ok(Code {
             main_file_name: main_file_name.to_string(),
+            encoding,
             file_content,
+            tokens: OnceCell::new(),
+            syntax_tree: OnceCell::new(),
         })
     }
 
-    pub fn get_file_content(&mut self) -> Result<&String, &'static str> {
-        if self.file_content.is_empty() {
-            self.file_content = match fs::read_to_string(&self.main_file_name) {
-                Ok(content) => content.to_uppercase(),
-                Err(_) => return Err("Could not load script file contents"),
-            }
-        }
+    pub fn get_file_content(&self) -> &String {
+        &self.file_content
+    }
+
+    pub fn get_tokens(&self) -> &[TokenInfo] {
+        &self.tokenize().0
+    }
+
+    /// Strings and block comments which are not properly closed
+    pub fn get_token_errors(&self) -> &[TokenError] {
+        &self.tokenize().1
+    }
+
+    /// Syntax tree, with errors of the statements which could not be parsed
+    pub fn get_syntax_tree(&self) -> (&Script, &[SyntaxError]) {
+        let (script, errors) = self
+            .syntax_tree
+            .get_or_init(|| parser::parse(self.get_tokens().to_vec()));
+
+        (script, errors)
+    }
+
+    fn tokenize(&self) -> &(Vec<TokenInfo>, Vec<TokenError>) {
+        self.tokens
+            .get_or_init(|| tokenizer::tokenize(&self.file_content))
+    }
+}
 
-        Ok(&self.file_content)
+fn load(file_name: &str) -> Result<(String, Encoding), LoadError> {
+    // Windows reports reading of a directory as denied access
+    if Path::new(file_name).is_dir() {
+        return Err(LoadError::IsDirectory {
+            path: file_name.to_string(),
+        });
     }
 
-    pub fn get_tokens(&mut self) -> Vec<TokenInfo> {
-        let content = self.get_file_content().unwrap();
+    match fs::read(file_name) {
+        Ok(bytes) => Ok(Encoding::decode(&bytes)),
+        Err(e) => Err(LoadError::new(file_name, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Code;
+    use std::fs;
+
+    #[test]
+    fn content_is_kept_after_loading() {
+        let path =
+            std::env::temp_dir().join(format!("thinclippy-code-{}.tbasic", std::process::id()));
+        fs::write(&path, "").unwrap();
+
+        let code = Code::new(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
 
-        tokenizer::get_tokens(content)
+        // Empty script is not read again, so the removed file does not matter
+        assert_eq!(code.get_file_content(), "");
+        assert!(code.get_tokens().is_empty());
+        assert!(code.get_token_errors().is_empty());
     }
 }
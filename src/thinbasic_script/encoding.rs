@@ -0,0 +1,76 @@
+use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+/// Text encoding the script file was saved in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    /// UTF-8 starting with byte order mark
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Legacy ANSI encoding, assumed for anything which is not valid UTF-8
+    Windows1252,
+}
+
+impl Encoding {
+    /// Detects the encoding of file contents and decodes them
+    ///
+    /// UTF-16 is recognized only by its byte order mark, which is never a part of the text.
+    pub fn decode(bytes: &[u8]) -> (String, Encoding) {
+        let (text, encoding) = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            (UTF_8.decode_with_bom_removal(bytes).0, Encoding::Utf8Bom)
+        } else if bytes.starts_with(&[0xFF, 0xFE]) {
+            (UTF_16LE.decode_with_bom_removal(bytes).0, Encoding::Utf16Le)
+        } else if bytes.starts_with(&[0xFE, 0xFF]) {
+            (UTF_16BE.decode_with_bom_removal(bytes).0, Encoding::Utf16Be)
+        } else if let Ok(text) = std::str::from_utf8(bytes) {
+            (text.into(), Encoding::Utf8)
+        } else {
+            (
+                WINDOWS_1252.decode_without_bom_handling(bytes).0,
+                Encoding::Windows1252,
+            )
+        };
+
+        (text.into_owned(), encoding)
+    }
+
+    /// Name as registered by IANA
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Bom => "utf-8",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+            Encoding::Windows1252 => "windows-1252",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+
+    #[test]
+    fn encoding_is_detected() {
+        assert_eq!(
+            Encoding::decode(b"a = 1"),
+            ("a = 1".to_string(), Encoding::Utf8)
+        );
+        assert_eq!(
+            Encoding::decode(b"\xEF\xBB\xBFa = 1"),
+            ("a = 1".to_string(), Encoding::Utf8Bom)
+        );
+        assert_eq!(
+            Encoding::decode(b"\xFF\xFEa\x00=\x00"),
+            ("a=".to_string(), Encoding::Utf16Le)
+        );
+        assert_eq!(
+            Encoding::decode(b"\xFE\xFF\x00a\x00="),
+            ("a=".to_string(), Encoding::Utf16Be)
+        );
+        assert_eq!(
+            Encoding::decode(b"P\xF8\xEDli\x9A \x80"),
+            ("Pøíliš €".to_string(), Encoding::Windows1252)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use super::Severity;
+use crate::tokenizer::Span;
 use std::fmt;
 
 pub struct IssueSummary {
@@ -6,7 +8,30 @@ pub struct IssueSummary {
     pub line: u32,
     pub pos: u32,
 
+    /// Last character of the offending code, same as start unless the rule knows better
+    pub end_line: u32,
+    pub end_pos: u32,
+
+    /// Stable code of the rule which reported the issue, such as TC0001
+    pub code: &'static str,
+    pub rule_name: &'static str,
+    pub severity: Severity,
+
     pub summary: String,
+
+    /// Set when a thinclippy:ignore or thinclippy:disable comment hides the issue
+    pub suppressed: bool,
+
+    /// Other places explaining the issue, such as start of the block it concerns
+    pub related: Vec<RelatedLocation>,
+}
+
+pub struct RelatedLocation {
+    pub line: u32,
+    pub pos: u32,
+    pub end_line: u32,
+    pub end_pos: u32,
+    pub message: String,
 }
 
 impl IssueSummary {
@@ -15,25 +40,80 @@ impl IssueSummary {
             script_file: script_file.to_string(),
             line,
             pos,
+            end_line: line,
+            end_pos: pos,
+            code: "",
+            rule_name: "",
+            severity: Severity::Error,
             summary: summary.to_string(),
+            suppressed: false,
+            related: vec![],
+        }
+    }
+
+    /// Issue covering the whole span, so reports can underline all of it
+    pub fn from_span(script_file: &str, span: &Span, summary: &str) -> IssueSummary {
+        let mut issue = IssueSummary::new(script_file, span.start.line, span.start.column, summary);
+
+        // Span end points after the last character, which is what we keep
+        if span.end.offset > span.start.offset && span.end.column > 1 {
+            issue.end_line = span.end.line;
+            issue.end_pos = span.end.column - 1;
         }
+
+        issue
+    }
+
+    pub fn add_related(&mut self, span: &Span, message: &str) {
+        let location = IssueSummary::from_span(&self.script_file, span, message);
+
+        self.related.push(RelatedLocation {
+            line: location.line,
+            pos: location.pos,
+            end_line: location.end_line,
+            end_pos: location.end_pos,
+            message: location.summary,
+        });
+    }
+
+    /// Marks the issue as reported by the given rule
+    pub fn set_rule(&mut self, code: &'static str, rule_name: &'static str, severity: Severity) {
+        self.code = code;
+        self.rule_name = rule_name;
+        self.severity = severity;
     }
 }
 
-// Custom transformation to str, for text representation
+// Custom transformation to str, in the file:line:col: severity: message form understood by editors
 impl fmt::Display for IssueSummary {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt.write_str(self.summary.as_str())?;
+        write!(
+            fmt,
+            "{}:{}:{}: {}: {}",
+            self.script_file, self.line, self.pos, self.severity, self.summary
+        )?;
 
-        fmt.write_str(", line: ")?;
-        fmt.write_str(&self.line.to_string()[..])?;
+        if !self.code.is_empty() {
+            write!(fmt, " [{}]", self.code)?;
+        }
+
+        Ok(())
+    }
+}
 
-        fmt.write_str(", pos: ")?;
-        fmt.write_str(&self.pos.to_string()[..])?;
+#[cfg(test)]
+mod tests {
+    use super::IssueSummary;
+    use crate::thinbasic_script::Severity;
 
-        fmt.write_str(" in ")?;
-        fmt.write_str(self.script_file.as_str())?;
+    #[test]
+    fn display_uses_compiler_style() {
+        let mut issue = IssueSummary::new("my script.tbasic", 12, 5, "Something is wrong");
+        issue.set_rule("TC0001", "alias-syntax", Severity::Warning);
 
-        Ok(())
+        assert_eq!(
+            issue.to_string(),
+            "my script.tbasic:12:5: warning: Something is wrong [TC0001]"
+        );
     }
 }
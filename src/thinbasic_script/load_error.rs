@@ -0,0 +1,84 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Reason why a script file could not be loaded
+#[derive(Debug)]
+pub enum LoadError {
+    NotFound { path: String, source: io::Error },
+    IsDirectory { path: String },
+    PermissionDenied { path: String, source: io::Error },
+    Io { path: String, source: io::Error },
+}
+
+impl LoadError {
+    pub fn new(path: &str, source: io::Error) -> LoadError {
+        let path = path.to_string();
+
+        match source.kind() {
+            io::ErrorKind::NotFound => LoadError::NotFound { path, source },
+            io::ErrorKind::IsADirectory => LoadError::IsDirectory { path },
+            io::ErrorKind::PermissionDenied => LoadError::PermissionDenied { path, source },
+            _ => LoadError::Io { path, source },
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::NotFound { path, .. } => {
+                write!(fmt, "{}: file not found, please check the path", path)
+            }
+            LoadError::IsDirectory { path } => write!(
+                fmt,
+                "{}: is a directory, scripts are searched there only by their extension",
+                path
+            ),
+            LoadError::PermissionDenied { path, .. } => write!(
+                fmt,
+                "{}: permission denied, please check the file access rights",
+                path
+            ),
+            LoadError::Io { path, source } => {
+                write!(fmt, "{}: could not be read, {}", path, source)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::NotFound { source, .. }
+            | LoadError::PermissionDenied { source, .. }
+            | LoadError::Io { source, .. } => Some(source),
+            LoadError::IsDirectory { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoadError;
+    use std::error::Error;
+    use std::io;
+
+    #[test]
+    fn error_kind_is_kept() {
+        let error = LoadError::new("a.tbasic", io::Error::from(io::ErrorKind::NotFound));
+
+        assert!(matches!(error, LoadError::NotFound { .. }));
+        assert!(error.source().is_some());
+        assert_eq!(
+            error.to_string(),
+            "a.tbasic: file not found, please check the path"
+        );
+
+        let error = LoadError::new("a.tbasic", io::Error::other("disk failure"));
+        assert_eq!(
+            error.to_string(),
+            "a.tbasic: could not be read, disk failure"
+        );
+    }
+}
@@ -1,7 +1,15 @@
 // The implementation is split across multiple files
 mod code;
+mod encoding;
 mod issue_summary;
+mod load_error;
+mod severity;
+mod suppression;
 
 // ...but we want to expose it directly under thinbasic_script
 pub use self::code::Code;
+pub use self::encoding::Encoding;
 pub use self::issue_summary::IssueSummary;
+pub use self::load_error::LoadError;
+pub use self::severity::Severity;
+pub use self::suppression::Suppressions;
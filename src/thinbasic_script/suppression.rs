@@ -0,0 +1,296 @@
+use super::IssueSummary;
+use crate::tokenizer::{TokenInfo, TokenType};
+
+const DIRECTIVE_PREFIX: &str = "thinclippy:";
+
+/// Single `thinclippy:ignore` or `thinclippy:disable` comment found in the script
+struct Directive {
+    line: u32,
+    pos: u32,
+
+    /// Rule codes or names, empty means all the rules
+    rules: Vec<String>,
+
+    used: bool,
+}
+
+/// Range of lines where issues of one rule (or all rules, for None) are suppressed
+struct Region {
+    directive: usize,
+    rule: Option<String>,
+    from_line: u32,
+    to_line: u32,
+}
+
+/// Suppression comments of a script
+///
+/// ' thinclippy:ignore TC0003           suppresses TC0003 on the next line
+/// ' thinclippy:disable alias-syntax    suppresses alias-syntax till thinclippy:enable
+/// ' thinclippy:enable                  ends all the disabled regions
+pub struct Suppressions {
+    directives: Vec<Directive>,
+    regions: Vec<Region>,
+}
+
+impl Suppressions {
+    pub fn from_tokens(tokens: &[TokenInfo]) -> Suppressions {
+        let mut suppressions = Suppressions {
+            directives: vec![],
+            regions: vec![],
+        };
+
+        let mut open_regions: Vec<usize> = vec![];
+
+        for token in tokens {
+            let comment = match &token.token_type {
+                TokenType::Comment(comment) => comment.trim(),
+                _ => continue,
+            };
+
+            // Slicing by bytes could split a character of a comment in other language
+            if !comment
+                .get(..DIRECTIVE_PREFIX.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(DIRECTIVE_PREFIX))
+            {
+                continue;
+            }
+
+            let mut words = comment[DIRECTIVE_PREFIX.len()..]
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|word| !word.is_empty());
+
+            let command = words.next().unwrap_or("").to_lowercase();
+            let rules: Vec<String> = words.map(|word| word.to_string()).collect();
+
+            match command.as_str() {
+                "ignore" => {
+                    let directive = suppressions.add_directive(token, rules);
+                    suppressions.add_regions(
+                        directive,
+                        token.span.start.line + 1,
+                        token.span.start.line + 1,
+                    );
+                }
+
+                "disable" => {
+                    let directive = suppressions.add_directive(token, rules);
+                    let first_region = suppressions.regions.len();
+                    suppressions.add_regions(directive, token.span.start.line, u32::MAX);
+
+                    open_regions.extend(first_region..suppressions.regions.len());
+                }
+
+                "enable" => {
+                    let regions = &mut suppressions.regions;
+
+                    open_regions.retain(|&index| {
+                        let region = &mut regions[index];
+
+                        let closes = rules.is_empty()
+                            || region
+                                .rule
+                                .as_ref()
+                                .is_some_and(|rule| contains_rule(&rules, rule));
+
+                        if closes {
+                            region.to_line = token.span.start.line;
+                        }
+
+                        !closes
+                    });
+                }
+
+                _ => {}
+            }
+        }
+
+        suppressions
+    }
+
+    fn add_directive(&mut self, token: &TokenInfo, rules: Vec<String>) -> usize {
+        self.directives.push(Directive {
+            line: token.span.start.line,
+            pos: token.span.start.column,
+            rules,
+            used: false,
+        });
+
+        self.directives.len() - 1
+    }
+
+    fn add_regions(&mut self, directive: usize, from_line: u32, to_line: u32) {
+        let rules = &self.directives[directive].rules;
+
+        if rules.is_empty() {
+            self.regions.push(Region {
+                directive,
+                rule: None,
+                from_line,
+                to_line,
+            });
+        } else {
+            for rule in rules {
+                self.regions.push(Region {
+                    directive,
+                    rule: Some(rule.clone()),
+                    from_line,
+                    to_line,
+                });
+            }
+        }
+    }
+
+    /// Marks the issue as suppressed when any suppression comment covers it
+    pub fn apply(&mut self, issue: &mut IssueSummary) {
+        for region in &self.regions {
+            if issue.line < region.from_line || issue.line > region.to_line {
+                continue;
+            }
+
+            let matches = match &region.rule {
+                None => true,
+                Some(rule) => {
+                    rule.eq_ignore_ascii_case(issue.code)
+                        || rule.eq_ignore_ascii_case(issue.rule_name)
+                }
+            };
+
+            if matches {
+                issue.suppressed = true;
+                self.directives[region.directive].used = true;
+            }
+        }
+    }
+
+    /// Reports suppression comments which did not hide any issue
+    ///
+    /// Comments listing only existing rules which were not run are left alone, as they
+    /// may be needed with another configuration. Unknown rules, such as misspelled ones,
+    /// are reported, as they cannot suppress anything.
+    pub fn unused_issues(
+        &self,
+        script_file: &str,
+        is_known: &dyn Fn(&str) -> bool,
+        is_active: &dyn Fn(&str) -> bool,
+    ) -> Vec<IssueSummary> {
+        self.directives
+            .iter()
+            .filter(|directive| !directive.used)
+            .filter_map(|directive| {
+                let summary = match directive.rules.iter().find(|rule| !is_known(rule)) {
+                    Some(unknown) => {
+                        format!("Suppression comment refers to unknown rule '{}'", unknown)
+                    }
+                    None if directive.rules.is_empty()
+                        || directive.rules.iter().any(|rule| is_active(rule)) =>
+                    {
+                        "Suppression comment does not match any issue".to_string()
+                    }
+                    None => return None,
+                };
+
+                Some(IssueSummary::new(
+                    script_file,
+                    directive.line,
+                    directive.pos,
+                    &summary,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn contains_rule(rules: &[String], rule: &str) -> bool {
+    rules.iter().any(|item| item.eq_ignore_ascii_case(rule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Suppressions;
+    use crate::thinbasic_script::IssueSummary;
+    use crate::tokenizer::get_tokens;
+
+    fn issue(line: u32, code: &'static str) -> IssueSummary {
+        let mut issue = IssueSummary::new("test.tbasic", line, 1, "Issue");
+        issue.code = code;
+        issue
+    }
+
+    #[test]
+    fn ignore_applies_to_next_line_only() {
+        let tokens = get_tokens("' thinclippy:ignore TC0001\na\nb");
+        let mut suppressions = Suppressions::from_tokens(&tokens);
+
+        let mut on_next_line = issue(2, "TC0001");
+        let mut on_later_line = issue(3, "TC0001");
+        let mut other_rule = issue(2, "TC0002");
+
+        suppressions.apply(&mut on_next_line);
+        suppressions.apply(&mut on_later_line);
+        suppressions.apply(&mut other_rule);
+
+        assert!(on_next_line.suppressed);
+        assert!(!on_later_line.suppressed);
+        assert!(!other_rule.suppressed);
+    }
+
+    #[test]
+    fn disable_applies_till_enable() {
+        let tokens = get_tokens("// thinclippy:disable\na\n' THINCLIPPY:enable\nb");
+        let mut suppressions = Suppressions::from_tokens(&tokens);
+
+        let mut inside = issue(2, "TC0002");
+        let mut after = issue(4, "TC0002");
+
+        suppressions.apply(&mut inside);
+        suppressions.apply(&mut after);
+
+        assert!(inside.suppressed);
+        assert!(!after.suppressed);
+    }
+
+    #[test]
+    fn non_ascii_comment_is_not_directive() {
+        let tokens = get_tokens(
+            "' Příliš žluťoučký kůň
+a",
+        );
+        let suppressions = Suppressions::from_tokens(&tokens);
+
+        assert!(suppressions.directives.is_empty());
+    }
+
+    #[test]
+    fn unused_suppression_is_reported() {
+        let tokens = get_tokens("a\n' thinclippy:ignore TC0001, TC0002\nb");
+        let mut suppressions = Suppressions::from_tokens(&tokens);
+
+        suppressions.apply(&mut issue(1, "TC0001"));
+
+        let unused = suppressions.unused_issues("test.tbasic", &|_| true, &|_| true);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].line, 2);
+
+        let unused = suppressions.unused_issues("test.tbasic", &|_| true, &|_| false);
+        assert!(unused.is_empty());
+    }
+
+    #[test]
+    fn misspelled_rule_is_reported() {
+        let tokens = get_tokens(
+            "' thinclippy:ignore TC00008
+a
+",
+        );
+        let suppressions = Suppressions::from_tokens(&tokens);
+
+        let is_known = |rule: &str| rule.eq_ignore_ascii_case("TC0008");
+        let unused = suppressions.unused_issues("test.tbasic", &is_known, &|_| false);
+
+        assert_eq!(unused.len(), 1);
+        assert_eq!(
+            unused[0].summary,
+            "Suppression comment refers to unknown rule 'TC00008'"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+/// Reserved word of the thinBasic core language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Alias,
+    Any,
+    As,
+    At,
+    Begin,
+    Boolean,
+    Byref,
+    Byte,
+    Byval,
+    Call,
+    Case,
+    Const,
+    Currency,
+    Declare,
+    Decr,
+    Dim,
+    Do,
+    Double,
+    Dword,
+    Each,
+    Else,
+    ElseIf,
+    End,
+    Exit,
+    Ext,
+    Extended,
+    For,
+    Function,
+    Global,
+    Gosub,
+    Goto,
+    If,
+    In,
+    Incr,
+    Integer,
+    Iterate,
+    Let,
+    Lib,
+    Local,
+    Long,
+    Loop,
+    New,
+    Next,
+    Number,
+    Optional,
+    Preserve,
+    Ptr,
+    Quad,
+    Redim,
+    Return,
+    Select,
+    Single,
+    Static,
+    Step,
+    String,
+    Sub,
+    Then,
+    To,
+    Type,
+    Union,
+    Until,
+    Variant,
+    Wend,
+    While,
+    With,
+    Word,
+}
+
+const KEYWORDS: [(&str, Keyword); 66] = [
+    ("ALIAS", Keyword::Alias),
+    ("ANY", Keyword::Any),
+    ("AS", Keyword::As),
+    ("AT", Keyword::At),
+    ("BEGIN", Keyword::Begin),
+    ("BOOLEAN", Keyword::Boolean),
+    ("BYREF", Keyword::Byref),
+    ("BYTE", Keyword::Byte),
+    ("BYVAL", Keyword::Byval),
+    ("CALL", Keyword::Call),
+    ("CASE", Keyword::Case),
+    ("CONST", Keyword::Const),
+    ("CURRENCY", Keyword::Currency),
+    ("DECLARE", Keyword::Declare),
+    ("DECR", Keyword::Decr),
+    ("DIM", Keyword::Dim),
+    ("DO", Keyword::Do),
+    ("DOUBLE", Keyword::Double),
+    ("DWORD", Keyword::Dword),
+    ("EACH", Keyword::Each),
+    ("ELSE", Keyword::Else),
+    ("ELSEIF", Keyword::ElseIf),
+    ("END", Keyword::End),
+    ("EXIT", Keyword::Exit),
+    ("EXT", Keyword::Ext),
+    ("EXTENDED", Keyword::Extended),
+    ("FOR", Keyword::For),
+    ("FUNCTION", Keyword::Function),
+    ("GLOBAL", Keyword::Global),
+    ("GOSUB", Keyword::Gosub),
+    ("GOTO", Keyword::Goto),
+    ("IF", Keyword::If),
+    ("IN", Keyword::In),
+    ("INCR", Keyword::Incr),
+    ("INTEGER", Keyword::Integer),
+    ("ITERATE", Keyword::Iterate),
+    ("LET", Keyword::Let),
+    ("LIB", Keyword::Lib),
+    ("LOCAL", Keyword::Local),
+    ("LONG", Keyword::Long),
+    ("LOOP", Keyword::Loop),
+    ("NEW", Keyword::New),
+    ("NEXT", Keyword::Next),
+    ("NUMBER", Keyword::Number),
+    ("OPTIONAL", Keyword::Optional),
+    ("PRESERVE", Keyword::Preserve),
+    ("PTR", Keyword::Ptr),
+    ("QUAD", Keyword::Quad),
+    ("REDIM", Keyword::Redim),
+    ("RETURN", Keyword::Return),
+    ("SELECT", Keyword::Select),
+    ("SINGLE", Keyword::Single),
+    ("STATIC", Keyword::Static),
+    ("STEP", Keyword::Step),
+    ("STRING", Keyword::String),
+    ("SUB", Keyword::Sub),
+    ("THEN", Keyword::Then),
+    ("TO", Keyword::To),
+    ("TYPE", Keyword::Type),
+    ("UNION", Keyword::Union),
+    ("UNTIL", Keyword::Until),
+    ("VARIANT", Keyword::Variant),
+    ("WEND", Keyword::Wend),
+    ("WHILE", Keyword::While),
+    ("WITH", Keyword::With),
+    ("WORD", Keyword::Word),
+];
+
+impl Keyword {
+    /// Looks up the keyword by its uppercase name
+    pub fn from_name(name: &str) -> Option<Keyword> {
+        KEYWORDS
+            .iter()
+            .find(|(keyword_name, _)| *keyword_name == name)
+            .map(|&(_, keyword)| keyword)
+    }
+}
+
+/// Preprocessor-like directive starting with #
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directive {
+    Bundle,
+    Compiled,
+    Default,
+    Else,
+    ElseIf,
+    EndCompiled,
+    EndIf,
+    If,
+    Include,
+    IncludeDir,
+    MinVersion,
+    Resource,
+    ScriptVersion,
+    /// Directive not known to thinClippy, its name is kept in the token text
+    Other,
+}
+
+const DIRECTIVES: [(&str, Directive); 13] = [
+    ("#BUNDLE", Directive::Bundle),
+    ("#COMPILED", Directive::Compiled),
+    ("#DEFAULT", Directive::Default),
+    ("#ELSE", Directive::Else),
+    ("#ELSEIF", Directive::ElseIf),
+    ("#ENDCOMPILED", Directive::EndCompiled),
+    ("#ENDIF", Directive::EndIf),
+    ("#IF", Directive::If),
+    ("#INCLUDE", Directive::Include),
+    ("#INCLUDEDIR", Directive::IncludeDir),
+    ("#MINVERSION", Directive::MinVersion),
+    ("#RESOURCE", Directive::Resource),
+    ("#SCRIPTVERSION", Directive::ScriptVersion),
+];
+
+impl Directive {
+    /// Looks up the directive by its uppercase name including #
+    pub fn from_name(name: &str) -> Directive {
+        DIRECTIVES
+            .iter()
+            .find(|(directive_name, _)| *directive_name == name)
+            .map_or(Directive::Other, |&(_, directive)| directive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Directive, Keyword, KEYWORDS};
+
+    #[test]
+    fn keyword_table_is_consistent() {
+        for (name, keyword) in KEYWORDS.iter() {
+            assert_eq!(Keyword::from_name(name), Some(*keyword));
+        }
+
+        assert_eq!(Keyword::from_name("MYVARIABLE"), None);
+        assert_eq!(Directive::from_name("#COMPILED"), Directive::Compiled);
+        assert_eq!(Directive::from_name("#PRAGMA"), Directive::Other);
+    }
+}
@@ -1,94 +1,402 @@
-use std::iter::Peekable;
+mod keyword;
+mod operator;
+mod token_cursor;
+mod trivia;
+
+pub use self::keyword::{Directive, Keyword};
+pub use self::operator::{Comparator, Operator};
+pub use self::token_cursor::TokenCursor;
+pub use self::trivia::{SyntaxToken, SyntaxTokens};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenInfo {
     pub token_type: TokenType,
+    /// Token as it was written in the source, with the original letter case
+    pub text: String,
+    pub span: Span,
+}
+
+/// Place in the source; line and column start at 1, column counts characters, not bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub offset: usize,
     pub line: u32,
-    pub pos: u32,
+    pub column: u32,
+}
+
+/// Part of the source, the end points right after the last character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Problem found while splitting the input, spanning the broken token
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenError {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl Radix {
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => "binary",
+            Radix::Octal => "octal",
+            Radix::Decimal => "decimal",
+            Radix::Hexadecimal => "hexadecimal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberValue {
+    Integer(i64),
+    Float(f64),
+}
+
+/// Numeric literal, such as 42, 1.5E-3, &HFF, &B1010, &O17 or 10&&
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberLiteral {
+    pub radix: Radix,
+
+    /// Parsed value, or the reason why the literal is malformed
+    pub value: Result<NumberValue, String>,
+
+    /// Type suffix, such as %, &, &&, !, #, ## or @
+    pub suffix: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     Whitespace,
     EndOfLine,
+    /// Underscore joining the next line, with whatever follows it till the end of line
+    LineContinuation,
     Paren(char),
     EqualSign,
-    Operator(char),
-    Comparator(String),
-    Number(String),
-    Symbol(String),
+    Operator(Operator),
+    /// +=, -= and alike, applying the operator to the assigned variable
+    CompoundAssignment(Operator),
+    Comparator(Comparator),
+    Bracket(char),
+    /// Member access, such as in MyType.Member
+    Dot,
+    /// Separator of statements on the same line
+    Colon,
+    Semicolon,
+    Number(NumberLiteral),
+    Keyword(Keyword),
+    /// Name of variable, function or anything else which is not a keyword, in uppercase
+    Identifier(String),
+    /// %NUMERIC_EQUATE or $STRING_EQUATE, in uppercase
+    Equate(String),
+    Directive(Directive),
     Text(String),
     Comment(String),
     Unknown(char),
     Comma,
 }
 
-fn get_number<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>, pos: &mut u32) -> String {
-    let mut token = String::new();
+impl TokenType {
+    /// Whitespace, comments and line continuations do not change the meaning of code
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            TokenType::Whitespace | TokenType::LineContinuation | TokenType::Comment(_)
+        )
+    }
+}
 
-    while let Some(&c) = char_iter.peek() {
-        match c {
-            '0'...'9' | '.' => {
-                char_iter.next();
-                token.push(c);
-                *pos += 1;
-            }
+/// Walks the input character by character, keeping track of the position
+#[derive(Clone)]
+struct Cursor<'a> {
+    input: &'a str,
+    position: Position,
+}
 
-            _ => return token,
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Cursor<'a> {
+        Cursor {
+            input,
+            position: Position {
+                offset: 0,
+                line: 1,
+                column: 1,
+            },
         }
     }
-    token
-}
 
-fn get_comparator<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>, pos: &mut u32) -> String {
-    let mut token = String::new();
+    fn peek(&self) -> Option<char> {
+        self.input[self.position.offset..].chars().next()
+    }
 
-    while let Some(&c) = char_iter.peek() {
-        match c {
-            '<' | '>' | '=' => {
-                char_iter.next();
-                token.push(c);
-                *pos += 1;
-            }
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.input[self.position.offset..].chars().nth(n)
+    }
 
-            _ => return token,
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+
+        self.position.offset += c.len_utf8();
+
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
         }
+
+        Some(c)
+    }
+
+    fn span_from(&self, start: Position) -> Span {
+        Span {
+            start,
+            end: self.position,
+        }
+    }
+
+    fn text_from(&self, start: Position) -> &'a str {
+        &self.input[start.offset..self.position.offset]
     }
-    token
 }
 
-fn get_whitespace<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>, pos: &mut u32) -> String {
-    let mut token = String::new();
+fn get_digits(cursor: &mut Cursor, is_digit: fn(char) -> bool) {
+    while let Some(c) = cursor.peek() {
+        if !is_digit(c) {
+            return;
+        }
 
-    while let Some(&c) = char_iter.peek() {
-        match c {
-            ' ' | '\t' => {
-                char_iter.next();
-                token.push(c);
-                *pos += 1;
+        cursor.next();
+    }
+}
+
+/// Checks a number literal starts at the current position, not just a dot or an operator
+fn is_number_ahead(cursor: &Cursor) -> bool {
+    match cursor.peek() {
+        Some('0'..='9') => true,
+        Some('.') => cursor.peek_nth(1).is_some_and(|c| c.is_ascii_digit()),
+        Some('&') => is_radix_prefix(cursor),
+        _ => false,
+    }
+}
+
+/// Checks &H, &B or &O prefix followed by at least one letter or digit is ahead
+fn is_radix_prefix(cursor: &Cursor) -> bool {
+    cursor.peek() == Some('&')
+        && matches!(cursor.peek_nth(1), Some('H' | 'h' | 'B' | 'b' | 'O' | 'o'))
+        && cursor
+            .peek_nth(2)
+            .is_some_and(|c| c.is_ascii_alphanumeric())
+}
+
+/// Exponent is taken only when digits follow, so 1ELSE stays a number and a keyword
+fn is_exponent(cursor: &Cursor, n: usize) -> bool {
+    if !matches!(cursor.peek_nth(n), Some('E' | 'e')) {
+        return false;
+    }
+
+    match cursor.peek_nth(n + 1) {
+        Some('+') | Some('-') => cursor.peek_nth(n + 2).is_some_and(|c| c.is_ascii_digit()),
+        Some(c) => c.is_ascii_digit(),
+        None => false,
+    }
+}
+
+/// Dot followed by a name is member access rather than a decimal point, as in 2.5 or 2.E3
+fn is_member_access(cursor: &Cursor) -> bool {
+    cursor.peek() == Some('.')
+        && cursor
+            .peek_nth(1)
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && !is_exponent(cursor, 1)
+}
+
+fn get_type_suffix(cursor: &mut Cursor) -> Option<String> {
+    let c = cursor.peek()?;
+
+    if !matches!(c, '%' | '&' | '!' | '#' | '@') {
+        return None;
+    }
+
+    let doubled = matches!(c, '&' | '#' | '@') && cursor.peek_nth(1) == Some(c);
+    let length = if doubled { 2 } else { 1 };
+
+    // 1&x or 1#COMPILED would be ambiguous, suffix must not be glued to anything else
+    if cursor.peek_nth(length).is_some_and(|next| {
+        next.is_alphanumeric() || matches!(next, '"' | '_' | '$' | '%' | '#' | '&')
+    }) {
+        return None;
+    }
+
+    let start = cursor.position;
+    for _ in 0..length {
+        cursor.next();
+    }
+
+    Some(cursor.text_from(start).to_string())
+}
+
+fn get_number(cursor: &mut Cursor) -> NumberLiteral {
+    let start = cursor.position;
+
+    let radix;
+    let value;
+
+    if is_radix_prefix(cursor) {
+        // &
+        cursor.next();
+
+        let prefix = cursor.next().unwrap_or('H');
+
+        let digits_start = cursor.position;
+        get_digits(cursor, |c| c.is_ascii_alphanumeric());
+        let digits = cursor.text_from(digits_start);
+
+        radix = match prefix.to_ascii_uppercase() {
+            'B' => Radix::Binary,
+            'O' => Radix::Octal,
+            _ => Radix::Hexadecimal,
+        };
+
+        value = match i64::from_str_radix(digits, radix.base()) {
+            Ok(number) => Ok(NumberValue::Integer(number)),
+            Err(_) if digits.chars().all(|c| c.is_digit(radix.base())) => {
+                Err("value is too large".to_string())
+            }
+            Err(_) => Err(format!("invalid digits for {} number", radix.name())),
+        };
+    } else {
+        radix = Radix::Decimal;
+
+        while let Some(c) = cursor.peek() {
+            if !(c.is_ascii_digit() || c == '.' && !is_member_access(cursor)) {
+                break;
             }
 
-            _ => return token,
+            cursor.next();
+        }
+
+        let mut is_float = cursor.text_from(start).contains('.');
+
+        if is_exponent(cursor, 0) {
+            is_float = true;
+
+            // E and optional sign
+            cursor.next();
+            if matches!(cursor.peek(), Some('+' | '-')) {
+                cursor.next();
+            }
+
+            get_digits(cursor, |c| c.is_ascii_digit());
         }
+
+        let token = cursor.text_from(start);
+
+        value = if token.matches('.').count() > 1 {
+            Err("more than one decimal point".to_string())
+        } else if is_float {
+            token
+                .parse::<f64>()
+                .map(NumberValue::Float)
+                .map_err(|_| "invalid decimal number".to_string())
+        } else {
+            token
+                .parse::<i64>()
+                .map(NumberValue::Integer)
+                .map_err(|_| "value is too large".to_string())
+        };
+    }
+
+    let suffix = get_type_suffix(cursor);
+
+    NumberLiteral {
+        radix,
+        value,
+        suffix,
     }
-    token
 }
 
-fn get_symbol<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>, pos: &mut u32) -> String {
+fn get_comparator(cursor: &mut Cursor) -> Comparator {
+    let first = cursor.next();
+    let second = cursor.peek();
+
+    let (comparator, length) = match (first, second) {
+        (Some('<'), Some('=')) => (Comparator::LessOrEqual, 2),
+        (Some('<'), Some('>')) => (Comparator::NotEqual, 2),
+        (Some('>'), Some('=')) => (Comparator::GreaterOrEqual, 2),
+        (Some('<'), _) => (Comparator::Less, 1),
+        _ => (Comparator::Greater, 1),
+    };
+
+    if length == 2 {
+        cursor.next();
+    }
+
+    comparator
+}
+
+/// Operator such as + or +=, returns None for characters not being operators
+fn get_operator(cursor: &mut Cursor) -> Option<TokenType> {
+    let operator = Operator::from_char(cursor.peek()?)?;
+    cursor.next();
+
+    let compound = matches!(
+        operator,
+        Operator::Add
+            | Operator::Subtract
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::IntegerDivide
+            | Operator::Concatenate
+    );
+
+    if compound && cursor.peek() == Some('=') {
+        cursor.next();
+        return Some(TokenType::CompoundAssignment(operator));
+    }
+
+    Some(TokenType::Operator(operator))
+}
+
+fn get_whitespace(cursor: &mut Cursor) {
+    while let Some(' ') | Some('\t') = cursor.peek() {
+        cursor.next();
+    }
+}
+
+/// Name starting with a letter or one of #$%_ and continuing with digits as well
+fn get_symbol(cursor: &mut Cursor) -> String {
     let mut token = String::new();
 
-    while let Some(&c) = char_iter.peek() {
+    while let Some(c) = cursor.peek() {
         match c {
-            'A'...'Z' | '#' | '$' | '%' | '_' => {
-                char_iter.next();
+            'A'..='Z' | 'a'..='z' | '#' | '$' | '%' | '_' => {
+                cursor.next();
                 token.push(c);
-                *pos += 1;
             }
 
-            'a'...'z' => {
-                char_iter.next();
-                token.push(c.to_string().to_uppercase().chars().next().unwrap());
-                *pos += 1;
+            '0'..='9' if !token.is_empty() => {
+                cursor.next();
+                token.push(c);
             }
 
             _ => return token,
@@ -98,24 +406,35 @@ fn get_symbol<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>, pos: &mut u
     token
 }
 
-fn get_single_comment<T: Iterator<Item = char>>(
-    char_iter: &mut Peekable<T>,
-    pos: &mut u32,
-) -> String {
+fn classify_word(normalized_word: String) -> TokenType {
+    match normalized_word.chars().next() {
+        Some('#') => TokenType::Directive(Directive::from_name(&normalized_word)),
+        Some('%') | Some('$') => TokenType::Equate(normalized_word),
+        _ => {
+            if let Some(operator) = Operator::from_word(&normalized_word) {
+                TokenType::Operator(operator)
+            } else if let Some(keyword) = Keyword::from_name(&normalized_word) {
+                TokenType::Keyword(keyword)
+            } else {
+                TokenType::Identifier(normalized_word)
+            }
+        }
+    }
+}
+
+/// Comment till the end of line, which is left for the caller
+fn get_single_comment(cursor: &mut Cursor) -> String {
     let mut token = String::new();
 
-    while let Some(&c) = char_iter.peek() {
+    while let Some(c) = cursor.peek() {
         match c {
             '\n' => return token,
 
-            '\r' => {
-                char_iter.next();
-            }
+            '\r' if cursor.peek_nth(1) == Some('\n') => return token,
 
             _ => {
-                char_iter.next();
+                cursor.next();
                 token.push(c);
-                *pos += 1;
             }
         }
     }
@@ -123,318 +442,278 @@ fn get_single_comment<T: Iterator<Item = char>>(
     token
 }
 
-fn get_block_comment<T: Iterator<Item = char>>(
-    char_iter: &mut Peekable<T>,
-    line_no: &mut u32,
-    pos: &mut u32,
-) -> String {
+/// Returns None when the input ends before the closing */
+fn get_block_comment(cursor: &mut Cursor) -> Option<String> {
     let mut token = String::new();
 
-    while let Some(&c) = char_iter.peek() {
+    while let Some(c) = cursor.next() {
         match c {
-            '*' => {
-                char_iter.next();
-                *pos += 1;
-
-                if char_iter.peek() == Some(&'/') {
-                    // Detecting ending */
-                    char_iter.next();
-                    *pos += 1;
+            '*' if cursor.peek() == Some('/') => {
+                // Detecting ending */
+                cursor.next();
 
-                    return token;
-                } else {
-                    *pos += 1;
-                    token.push(c);
-                }
+                return Some(token);
             }
 
-            '\r' => {
-                char_iter.next();
-            }
+            '\r' => {}
 
-            '\n' => {
-                char_iter.next();
-                *line_no += 1;
-                *pos = 0;
-                token.push(c);
-            }
-
-            _ => {
-                char_iter.next();
-                *pos += 1;
-                token.push(c);
-            }
+            _ => token.push(c),
         }
     }
 
-    token
+    None
 }
 
-fn get_text<T: Iterator<Item = char>>(
-    char_iter: &mut Peekable<T>,
-    line_no: &mut u32,
-    pos: &mut u32,
-) -> String {
+/// Returns the text and whether it was terminated by the closing quote on the same line
+fn get_text(cursor: &mut Cursor) -> (String, bool) {
     let mut token = String::new();
     let mut quote_level = 0;
 
-    while let Some(&c) = char_iter.peek() {
+    while let Some(c) = cursor.peek() {
         match c {
             '\"' => {
                 quote_level += 1;
 
-                char_iter.next();
-                *pos += 1;
+                cursor.next();
                 token.push(c);
 
                 if quote_level == 2 {
-                    if char_iter.peek() == Some(&'\"') {
-                        char_iter.next();
-                        *pos += 1;
+                    if cursor.peek() == Some('\"') {
+                        cursor.next();
                         token.push(c);
                         quote_level -= 1;
                     } else {
-                        return token;
+                        return (token, true);
                     }
                 }
             }
 
-            '\r' => {
-                char_iter.next();
-            }
-
-            '\n' => {
-                char_iter.next();
-                *line_no += 1;
-                *pos = 0;
-                token.push(c);
-            }
+            // Strings cannot span multiple lines, the end of line is left for the caller
+            '\r' | '\n' => return (token, false),
 
             _ => {
-                char_iter.next();
-                *pos += 1;
+                cursor.next();
                 token.push(c);
             }
         }
     }
 
-    token
+    (token, false)
 }
 
-fn skip_till_the_end_of_line<T: Iterator<Item = char>>(char_iter: &mut Peekable<T>) {
-    while let Some(&c) = char_iter.peek() {
-        match c {
-            '\n' => {
-                char_iter.next();
-                return;
-            }
-
-            _ => {
-                char_iter.next();
-            }
+fn skip_till_the_end_of_line(cursor: &mut Cursor) {
+    while let Some(c) = cursor.next() {
+        if c == '\n' {
+            return;
         }
     }
 }
 
+/// Tokens without the errors, the analysis gets both of them from `Code`
+#[cfg(test)]
 pub fn get_tokens(input: &str) -> Vec<TokenInfo> {
+    tokenize(input).0
+}
+
+/// Splits the input to tokens, reporting literals which are not properly closed
+///
+/// Unterminated string or block comment ends at the end of its line, so the rest of
+/// the script is still tokenized as code.
+pub fn tokenize(input: &str) -> (Vec<TokenInfo>, Vec<TokenError>) {
     let mut simple_tokens: Vec<TokenInfo> = vec![];
+    let mut errors: Vec<TokenError> = vec![];
+
+    let mut cursor = Cursor::new(input);
 
-    let mut char_iter = input.chars().peekable();
+    let token = |token_type: TokenType, cursor: &Cursor, start: Position| TokenInfo {
+        token_type,
+        text: cursor.text_from(start).to_string(),
+        span: cursor.span_from(start),
+    };
 
-    let mut line_no = 1;
-    let mut pos_no = 0;
-    let mut start_pos_no;
+    while let Some(c) = cursor.peek() {
+        let start = cursor.position;
 
-    while let Some(&c) = char_iter.peek() {
-        start_pos_no = pos_no + 1;
         match c {
-            'A'...'Z' | 'a'...'z' | '#' | '$' | '%' | '_' => {
-                let symbol = get_symbol(&mut char_iter, &mut pos_no);
-
-                if symbol == "REM" {
-                    let comment_token = get_single_comment(&mut char_iter, &mut pos_no);
-                    simple_tokens.push(TokenInfo {
-                        token_type: TokenType::Comment(comment_token),
-                        line: line_no,
-                        pos: start_pos_no,
-                    });
-                } else if symbol == "_" {
-                    // Line continuation
-
-                    if char_iter.peek() == Some(&' ')
-                        || char_iter.peek() == Some(&'\t')
-                        || char_iter.peek() == Some(&'\r')
-                        || char_iter.peek() == Some(&'\n')
-                    {
-                        skip_till_the_end_of_line(&mut char_iter);
-                        line_no += 1;
-                        pos_no = 0;
-                    }
+            'A'..='Z' | 'a'..='z' | '#' | '$' | '%' | '_' => {
+                let symbol = get_symbol(&mut cursor);
+                let normalized_symbol = symbol.to_uppercase();
+
+                if normalized_symbol == "REM" {
+                    let comment_token = get_single_comment(&mut cursor);
+                    simple_tokens.push(token(TokenType::Comment(comment_token), &cursor, start));
+                } else if symbol == "_"
+                    && matches!(cursor.peek(), Some(' ' | '\t' | '\r' | '\n') | None)
+                {
+                    skip_till_the_end_of_line(&mut cursor);
+                    simple_tokens.push(token(TokenType::LineContinuation, &cursor, start));
                 } else {
-                    simple_tokens.push(TokenInfo {
-                        token_type: TokenType::Symbol(symbol),
-                        line: line_no,
-                        pos: start_pos_no,
-                    });
+                    simple_tokens.push(token(classify_word(normalized_symbol), &cursor, start));
                 }
             }
 
-            '0'...'9' | '.' => {
-                let number_token = get_number(&mut char_iter, &mut pos_no);
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Number(number_token),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+            '0'..='9' | '.' | '&' if is_number_ahead(&cursor) => {
+                let number_literal = get_number(&mut cursor);
+                simple_tokens.push(token(TokenType::Number(number_literal), &cursor, start));
             }
 
             '<' | '>' => {
-                let comparator_token = get_comparator(&mut char_iter, &mut pos_no);
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Comparator(comparator_token),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                let comparator = get_comparator(&mut cursor);
+                simple_tokens.push(token(TokenType::Comparator(comparator), &cursor, start));
             }
 
-            '/' => {
-                char_iter.next(); // Absorb first /
+            '/' if matches!(cursor.peek_nth(1), Some('/' | '*')) => {
+                cursor.next(); // Absorb first /
 
-                if char_iter.peek() == Some(&'/') {
-                    char_iter.next(); // Absorb second /
+                if cursor.peek() == Some('/') {
+                    cursor.next(); // Absorb second /
 
-                    let comment_token = get_single_comment(&mut char_iter, &mut pos_no);
-                    simple_tokens.push(TokenInfo {
-                        token_type: TokenType::Comment(comment_token),
-                        line: line_no,
-                        pos: start_pos_no,
-                    });
-                } else if char_iter.peek() == Some(&'*') {
-                    char_iter.next(); // Absorb *
-
-                    let comment_token =
-                        get_block_comment(&mut char_iter, &mut line_no, &mut pos_no);
-                    simple_tokens.push(TokenInfo {
-                        token_type: TokenType::Comment(comment_token),
-                        line: line_no,
-                        pos: start_pos_no,
-                    });
-                } else {
-                    simple_tokens.push(TokenInfo {
-                        token_type: TokenType::Operator(c),
-                        line: line_no,
-                        pos: start_pos_no,
-                    });
+                    let comment_token = get_single_comment(&mut cursor);
+                    simple_tokens.push(token(TokenType::Comment(comment_token), &cursor, start));
+                } else if cursor.peek() == Some('*') {
+                    cursor.next(); // Absorb *
+
+                    let restart_cursor = cursor.clone();
+
+                    match get_block_comment(&mut cursor) {
+                        Some(comment_token) => simple_tokens.push(token(
+                            TokenType::Comment(comment_token),
+                            &cursor,
+                            start,
+                        )),
+
+                        None => {
+                            cursor = restart_cursor;
+
+                            let comment_token = get_single_comment(&mut cursor);
+
+                            errors.push(TokenError {
+                                span: cursor.span_from(start),
+                                message: "Block comment is not closed by */".to_string(),
+                            });
+
+                            simple_tokens.push(token(
+                                TokenType::Comment(comment_token),
+                                &cursor,
+                                start,
+                            ));
+                        }
+                    }
                 }
             }
 
-            '+' | '*' | '-' | '&' => {
-                char_iter.next();
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Operator(c),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+            '+' | '-' | '*' | '/' | '\\' | '^' | '&' => {
+                if let Some(operator_token) = get_operator(&mut cursor) {
+                    simple_tokens.push(token(operator_token, &cursor, start));
+                }
             }
 
             ' ' | '\t' => {
-                let _whitespace = get_whitespace(&mut char_iter, &mut pos_no);
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Whitespace,
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                get_whitespace(&mut cursor);
+                simple_tokens.push(token(TokenType::Whitespace, &cursor, start));
             }
 
             '(' | ')' => {
-                char_iter.next();
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Paren(c),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                cursor.next();
+                simple_tokens.push(token(TokenType::Paren(c), &cursor, start));
             }
 
             '\'' => {
-                char_iter.next(); // Absorb '
-
-                let comment_token = get_single_comment(&mut char_iter, &mut pos_no);
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Comment(comment_token),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                cursor.next(); // Absorb '
+
+                let comment_token = get_single_comment(&mut cursor);
+                simple_tokens.push(token(TokenType::Comment(comment_token), &cursor, start));
             }
 
             '"' => {
-                let text_string = get_text(&mut char_iter, &mut line_no, &mut pos_no);
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Text(text_string),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                let (text_string, terminated) = get_text(&mut cursor);
+
+                if !terminated {
+                    errors.push(TokenError {
+                        span: cursor.span_from(start),
+                        message: "String is not closed by quote on the same line".to_string(),
+                    });
+                }
+
+                simple_tokens.push(token(TokenType::Text(text_string), &cursor, start));
+            }
+
+            '\r' if cursor.peek_nth(1) == Some('\n') => {
+                cursor.next();
+                cursor.next();
+                simple_tokens.push(token(TokenType::EndOfLine, &cursor, start));
+            }
+
+            // Byte order mark is not a part of the first line, but the source must be kept whole
+            '\u{feff}' if start.offset == 0 => {
+                cursor.position.offset += c.len_utf8();
+                simple_tokens.push(token(TokenType::Whitespace, &cursor, start));
             }
 
             '\r' => {
-                char_iter.next();
+                cursor.next();
+                simple_tokens.push(token(TokenType::Whitespace, &cursor, start));
             }
 
             '\n' => {
-                char_iter.next();
-                line_no += 1;
-                pos_no = 0;
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::EndOfLine,
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                cursor.next();
+                simple_tokens.push(token(TokenType::EndOfLine, &cursor, start));
             }
 
             '=' => {
-                char_iter.next();
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::EqualSign,
-                    line: line_no,
-                    pos: start_pos_no,
-                });
-                pos_no += 1;
+                cursor.next();
+                simple_tokens.push(token(TokenType::EqualSign, &cursor, start));
             }
 
             ',' => {
-                char_iter.next();
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Comma,
-                    line: line_no,
-                    pos: start_pos_no,
-                });
-                pos_no += 1;
+                cursor.next();
+                simple_tokens.push(token(TokenType::Comma, &cursor, start));
+            }
+
+            '[' | ']' => {
+                cursor.next();
+                simple_tokens.push(token(TokenType::Bracket(c), &cursor, start));
+            }
+
+            '.' => {
+                cursor.next();
+                simple_tokens.push(token(TokenType::Dot, &cursor, start));
             }
 
-            '\u{feff}' => {
-                // Just BOM
-                char_iter.next();
-                pos_no += 1;
+            ':' => {
+                cursor.next();
+                simple_tokens.push(token(TokenType::Colon, &cursor, start));
+            }
+
+            ';' => {
+                cursor.next();
+                simple_tokens.push(token(TokenType::Semicolon, &cursor, start));
             }
 
             _ => {
-                char_iter.next();
-                simple_tokens.push(TokenInfo {
-                    token_type: TokenType::Unknown(c),
-                    line: line_no,
-                    pos: start_pos_no,
-                });
+                cursor.next();
+                simple_tokens.push(token(TokenType::Unknown(c), &cursor, start));
             }
         }
     }
 
-    simple_tokens
+    (simple_tokens, errors)
 }
 
 #[cfg(test)]
 pub mod tests {
 
-    use super::get_tokens;
-    use super::TokenType;
+    use super::{get_tokens, tokenize};
+    use super::{Comparator, Directive, Keyword, Operator, TokenType};
+    use super::{NumberLiteral, NumberValue, Radix};
+
+    fn number(radix: Radix, value: NumberValue, suffix: Option<&str>) -> TokenType {
+        TokenType::Number(NumberLiteral {
+            radix,
+            value: Ok(value),
+            suffix: suffix.map(|s| s.to_string()),
+        })
+    }
 
     #[test]
     fn get_number_works() {
@@ -443,9 +722,95 @@ pub mod tests {
         let tokens = get_tokens(code);
 
         assert_eq!(
-            tokens.get(0).unwrap().token_type,
-            TokenType::Number("1234".to_string())
+            tokens.first().unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Integer(1234), None)
         );
+        assert_eq!(tokens.first().unwrap().text, "1234");
+    }
+
+    #[test]
+    fn get_number_with_radix_prefix() {
+        let code = "&HFF &b1010 &O17";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            number(Radix::Hexadecimal, NumberValue::Integer(255), None)
+        );
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            number(Radix::Binary, NumberValue::Integer(10), None)
+        );
+        assert_eq!(tokens.get(2).unwrap().text, "&b1010");
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            number(Radix::Octal, NumberValue::Integer(15), None)
+        );
+    }
+
+    #[test]
+    fn get_number_scientific() {
+        let code = "1.5E-3 2e10";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(0.0015), None)
+        );
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(2e10), None)
+        );
+        assert_eq!(tokens.len(), 3);
+    }
+
+    #[test]
+    fn get_number_with_type_suffix() {
+        let code = "10&& + 2.5! + &HFF& & a";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Integer(10), Some("&&"))
+        );
+        assert_eq!(tokens.first().unwrap().text, "10&&");
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(2.5), Some("!"))
+        );
+        assert_eq!(
+            tokens.get(8).unwrap().token_type,
+            number(Radix::Hexadecimal, NumberValue::Integer(255), Some("&"))
+        );
+        assert_eq!(
+            tokens.get(10).unwrap().token_type,
+            TokenType::Operator(Operator::Concatenate)
+        );
+    }
+
+    #[test]
+    fn get_malformed_number() {
+        let code = "1..2 &HXYZ";
+
+        let tokens = get_tokens(code);
+
+        match &tokens.first().unwrap().token_type {
+            TokenType::Number(literal) => assert!(literal.value.is_err()),
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert_eq!(tokens.first().unwrap().text, "1..2");
+
+        match &tokens.get(2).unwrap().token_type {
+            TokenType::Number(literal) => {
+                assert_eq!(literal.radix, Radix::Hexadecimal);
+                assert!(literal.value.is_err());
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+        assert_eq!(tokens.get(2).unwrap().text, "&HXYZ");
     }
 
     #[test]
@@ -455,22 +820,150 @@ pub mod tests {
         let tokens = get_tokens(code);
 
         assert_eq!(
-            tokens.get(0).unwrap().token_type,
-            TokenType::Symbol("%CIAO_MY_FRIEND$".to_string())
+            tokens.first().unwrap().token_type,
+            TokenType::Equate("%CIAO_MY_FRIEND$".to_string())
         );
     }
 
+    #[test]
+    fn get_words_are_classified() {
+        let code = "#compiled $crlf local x AS long";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            TokenType::Directive(Directive::Compiled)
+        );
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            TokenType::Equate("$CRLF".to_string())
+        );
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            TokenType::Keyword(Keyword::Local)
+        );
+        assert_eq!(
+            tokens.get(6).unwrap().token_type,
+            TokenType::Identifier("X".to_string())
+        );
+        assert_eq!(
+            tokens.get(8).unwrap().token_type,
+            TokenType::Keyword(Keyword::As)
+        );
+        assert_eq!(
+            tokens.get(10).unwrap().token_type,
+            TokenType::Keyword(Keyword::Long)
+        );
+    }
+
+    #[test]
+    fn get_symbol_with_digits() {
+        let code = "myVar2 = x1$ + a2b";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            TokenType::Identifier("MYVAR2".to_string())
+        );
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            TokenType::Identifier("X1$".to_string())
+        );
+        assert_eq!(
+            tokens.get(8).unwrap().token_type,
+            TokenType::Identifier("A2B".to_string())
+        );
+        assert_eq!(tokens.len(), 9);
+    }
+
+    #[test]
+    fn get_number_with_leading_or_trailing_dot() {
+        let code = ".5 + 5. + 2.E3";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(0.5), None)
+        );
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(5.0), None)
+        );
+        assert_eq!(
+            tokens.get(8).unwrap().token_type,
+            number(Radix::Decimal, NumberValue::Float(2000.0), None)
+        );
+    }
+
+    #[test]
+    fn member_access_is_not_number() {
+        let code = "obj.member2 = arr(1).x + 2.y";
+
+        let types: Vec<TokenType> = get_tokens(code)
+            .into_iter()
+            .map(|token| token.token_type)
+            .filter(|token_type| *token_type != TokenType::Whitespace)
+            .collect();
+
+        assert_eq!(types[0], TokenType::Identifier("OBJ".to_string()));
+        assert_eq!(types[1], TokenType::Dot);
+        assert_eq!(types[2], TokenType::Identifier("MEMBER2".to_string()));
+        assert_eq!(types[8], TokenType::Dot);
+        assert_eq!(types[9], TokenType::Identifier("X".to_string()));
+        assert_eq!(
+            types[11],
+            number(Radix::Decimal, NumberValue::Integer(2), None)
+        );
+        assert_eq!(types[12], TokenType::Dot);
+    }
+
+    #[test]
+    fn get_symbol_keeps_original_spelling() {
+        let code = "Dim myVariable";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.first().unwrap().token_type,
+            TokenType::Keyword(Keyword::Dim)
+        );
+        assert_eq!(tokens.first().unwrap().text, "Dim");
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            TokenType::Identifier("MYVARIABLE".to_string())
+        );
+        assert_eq!(tokens.get(2).unwrap().text, "myVariable");
+    }
+
     #[test]
     fn get_operator_works() {
         let code = "a + b - c * d / e & f";
 
         let tokens = get_tokens(code);
 
-        assert_eq!(tokens.get(2).unwrap().token_type, TokenType::Operator('+'));
-        assert_eq!(tokens.get(6).unwrap().token_type, TokenType::Operator('-'));
-        assert_eq!(tokens.get(10).unwrap().token_type, TokenType::Operator('*'));
-        assert_eq!(tokens.get(14).unwrap().token_type, TokenType::Operator('/'));
-        assert_eq!(tokens.get(18).unwrap().token_type, TokenType::Operator('&'));
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            TokenType::Operator(Operator::Add)
+        );
+        assert_eq!(
+            tokens.get(6).unwrap().token_type,
+            TokenType::Operator(Operator::Subtract)
+        );
+        assert_eq!(
+            tokens.get(10).unwrap().token_type,
+            TokenType::Operator(Operator::Multiply)
+        );
+        assert_eq!(
+            tokens.get(14).unwrap().token_type,
+            TokenType::Operator(Operator::Divide)
+        );
+        assert_eq!(
+            tokens.get(18).unwrap().token_type,
+            TokenType::Operator(Operator::Concatenate)
+        );
     }
 
     #[test]
@@ -488,7 +981,7 @@ pub mod tests {
 
         let tokens = get_tokens(code);
 
-        assert_eq!(tokens.get(0).unwrap().token_type, TokenType::Paren('('));
+        assert_eq!(tokens.first().unwrap().token_type, TokenType::Paren('('));
         assert_eq!(tokens.get(2).unwrap().token_type, TokenType::Paren(')'));
     }
 
@@ -515,7 +1008,7 @@ pub mod tests {
         assert_eq!(tokens.get(5).unwrap().token_type, TokenType::EndOfLine);
         assert_eq!(
             tokens.get(6).unwrap().token_type,
-            TokenType::Symbol("INDEED".to_string())
+            TokenType::Identifier("INDEED".to_string())
         );
     }
 
@@ -532,7 +1025,7 @@ pub mod tests {
         assert_eq!(tokens.get(5).unwrap().token_type, TokenType::EndOfLine);
         assert_eq!(
             tokens.get(6).unwrap().token_type,
-            TokenType::Symbol("INDEED".to_string())
+            TokenType::Identifier("INDEED".to_string())
         );
     }
 
@@ -549,7 +1042,7 @@ pub mod tests {
         assert_eq!(tokens.get(3).unwrap().token_type, TokenType::Whitespace);
         assert_eq!(
             tokens.get(4).unwrap().token_type,
-            TokenType::Symbol("INDEED".to_string())
+            TokenType::Identifier("INDEED".to_string())
         );
     }
 
@@ -565,7 +1058,7 @@ pub mod tests {
         );
         assert_eq!(
             tokens.get(4).unwrap().token_type,
-            TokenType::Symbol("INDEED".to_string())
+            TokenType::Identifier("INDEED".to_string())
         );
     }
 
@@ -591,6 +1084,18 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn get_text_keeps_letter_case() {
+        let code = "hello = \"Ciao Bambino\"";
+
+        let tokens = get_tokens(code);
+
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            TokenType::Text("\"Ciao Bambino\"".to_string())
+        );
+    }
+
     #[test]
     fn get_text_string_with_inner_quotes() {
         let code = "hello = \"Ciao, dear \"\"bambino\"\"\"";
@@ -603,6 +1108,52 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn unterminated_text_ends_at_end_of_line() {
+        let code = "a = \"Ciao\nb = 1";
+
+        let (tokens, errors) = tokenize(code);
+
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            TokenType::Text("\"Ciao".to_string())
+        );
+        assert_eq!(tokens.get(5).unwrap().token_type, TokenType::EndOfLine);
+        assert_eq!(
+            tokens.get(6).unwrap().token_type,
+            TokenType::Identifier("B".to_string())
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            (errors[0].span.start.line, errors[0].span.start.column),
+            (1, 5)
+        );
+    }
+
+    #[test]
+    fn unterminated_block_comment_ends_at_end_of_line() {
+        let code = "a /* famous\nb";
+
+        let (tokens, errors) = tokenize(code);
+
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            TokenType::Comment(" famous".to_string())
+        );
+        assert_eq!(
+            tokens.get(4).unwrap().token_type,
+            TokenType::Identifier("B".to_string())
+        );
+        assert_eq!(tokens.get(4).unwrap().span.start.line, 2);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            (errors[0].span.start.line, errors[0].span.start.column),
+            (1, 3)
+        );
+    }
+
     #[test]
     fn get_equal_sign() {
         let code = "numero = 1";
@@ -620,22 +1171,47 @@ pub mod tests {
 
         assert_eq!(
             tokens.get(1).unwrap().token_type,
-            TokenType::Comparator(">=".to_string())
+            TokenType::Comparator(Comparator::GreaterOrEqual)
         );
         assert_eq!(
             tokens.get(5).unwrap().token_type,
-            TokenType::Comparator("<=".to_string())
+            TokenType::Comparator(Comparator::LessOrEqual)
         );
         assert_eq!(
             tokens.get(9).unwrap().token_type,
-            TokenType::Comparator("<".to_string())
+            TokenType::Comparator(Comparator::Less)
         );
         assert_eq!(
             tokens.get(13).unwrap().token_type,
-            TokenType::Comparator(">".to_string())
+            TokenType::Comparator(Comparator::Greater)
         );
     }
 
+    #[test]
+    fn get_full_operator_set() {
+        let code = "a += b\\2^3 <> c[1]:d.e; x -= 1 MOD y And Not z";
+
+        let types: Vec<TokenType> = get_tokens(code)
+            .into_iter()
+            .map(|token| token.token_type)
+            .filter(|token_type| *token_type != TokenType::Whitespace)
+            .collect();
+
+        assert_eq!(types[1], TokenType::CompoundAssignment(Operator::Add));
+        assert_eq!(types[3], TokenType::Operator(Operator::IntegerDivide));
+        assert_eq!(types[5], TokenType::Operator(Operator::Power));
+        assert_eq!(types[7], TokenType::Comparator(Comparator::NotEqual));
+        assert_eq!(types[9], TokenType::Bracket('['));
+        assert_eq!(types[11], TokenType::Bracket(']'));
+        assert_eq!(types[12], TokenType::Colon);
+        assert_eq!(types[14], TokenType::Dot);
+        assert_eq!(types[16], TokenType::Semicolon);
+        assert_eq!(types[18], TokenType::CompoundAssignment(Operator::Subtract));
+        assert_eq!(types[20], TokenType::Operator(Operator::Mod));
+        assert_eq!(types[22], TokenType::Operator(Operator::And));
+        assert_eq!(types[23], TokenType::Operator(Operator::Not));
+    }
+
     #[test]
     fn get_unknown() {
         let code = "this is ~";
@@ -651,9 +1227,9 @@ pub mod tests {
 
         let tokens = get_tokens(code);
 
-        assert_eq!(tokens.get(0).unwrap().line, 1u32);
-        assert_eq!(tokens.get(2).unwrap().line, 2u32);
-        assert_eq!(tokens.get(4).unwrap().line, 3u32);
+        assert_eq!(tokens.first().unwrap().span.start.line, 1u32);
+        assert_eq!(tokens.get(2).unwrap().span.start.line, 2u32);
+        assert_eq!(tokens.get(4).unwrap().span.start.line, 3u32);
     }
 
     #[test]
@@ -662,16 +1238,19 @@ pub mod tests {
 
         let tokens = get_tokens(code);
 
-        assert_eq!(tokens.get(0).unwrap().line, 1u32); // a
-        assert_eq!(tokens.get(1).unwrap().line, 1u32); // <space>
+        assert_eq!(tokens.first().unwrap().span.start.line, 1u32); // a
+        assert_eq!(tokens.get(1).unwrap().span.start.line, 1u32); // <space>
+        assert_eq!(tokens.get(2).unwrap().span.start.line, 1u32); // _
 
-        assert_eq!(tokens.get(2).unwrap().line, 2u32); // b
-        assert_eq!(tokens.get(3).unwrap().line, 2u32); // <space>
+        assert_eq!(tokens.get(3).unwrap().span.start.line, 2u32); // b
+        assert_eq!(tokens.get(4).unwrap().span.start.line, 2u32); // <space>
+        assert_eq!(tokens.get(5).unwrap().span.start.line, 2u32); // _
 
-        assert_eq!(tokens.get(4).unwrap().line, 3u32); // c
-        assert_eq!(tokens.get(5).unwrap().line, 3u32); // <space>
+        assert_eq!(tokens.get(6).unwrap().span.start.line, 3u32); // c
+        assert_eq!(tokens.get(7).unwrap().span.start.line, 3u32); // <space>
+        assert_eq!(tokens.get(8).unwrap().span.start.line, 3u32); // _
 
-        assert_eq!(tokens.get(6).unwrap().line, 4u32); // d
+        assert_eq!(tokens.get(9).unwrap().span.start.line, 4u32); // d
     }
 
     #[test]
@@ -680,131 +1259,114 @@ pub mod tests {
 
         let tokens = get_tokens(code);
 
-        assert_eq!(tokens.get(0).unwrap().token_type, TokenType::Whitespace);
+        assert_eq!(tokens.first().unwrap().token_type, TokenType::Whitespace);
         assert_eq!(
             tokens.get(1).unwrap().token_type,
-            TokenType::Symbol("WELL".to_string())
+            TokenType::Identifier("WELL".to_string())
         );
         assert_eq!(tokens.get(2).unwrap().token_type, TokenType::EndOfLine);
         assert_eq!(tokens.get(3).unwrap().token_type, TokenType::Whitespace);
         assert_eq!(
             tokens.get(4).unwrap().token_type,
-            TokenType::Symbol("THIS".to_string())
+            TokenType::Identifier("THIS".to_string())
         );
         assert_eq!(tokens.get(5).unwrap().token_type, TokenType::EndOfLine);
         assert_eq!(tokens.get(6).unwrap().token_type, TokenType::Whitespace);
         assert_eq!(
             tokens.get(7).unwrap().token_type,
-            TokenType::Symbol("WORKS".to_string())
+            TokenType::Identifier("WORKS".to_string())
         );
 
-        assert_eq!(tokens.get(1).unwrap().pos, 2u32);
-        assert_eq!(tokens.get(4).unwrap().pos, 3u32);
-        assert_eq!(tokens.get(7).unwrap().pos, 4u32);
+        assert_eq!(tokens.get(1).unwrap().span.start.column, 2u32);
+        assert_eq!(tokens.get(4).unwrap().span.start.column, 3u32);
+        assert_eq!(tokens.get(7).unwrap().span.start.column, 4u32);
     }
 
-}
+    #[test]
+    fn span_offsets_and_end() {
+        let code = "ab = 12\r\ncd";
 
-pub fn parse_whitespace(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let next_token = token_iter.peek();
-    if next_token.unwrap().token_type == TokenType::Whitespace {
-        token_iter.next();
-        true
-    } else {
-        false
-    }
-}
+        let tokens = get_tokens(code);
 
-pub fn parse_symbol(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-    symbol: &str,
-) -> bool {
-    let next_token = token_iter.peek();
-    if next_token.unwrap().token_type == TokenType::Symbol(symbol.to_string()) {
-        token_iter.next();
-        true
-    } else {
-        false
-    }
-}
+        let number = tokens.get(4).unwrap();
+        assert_eq!((number.span.start.offset, number.span.end.offset), (5, 7));
+        assert_eq!((number.span.start.column, number.span.end.column), (6, 8));
 
-pub fn parse_any_symbol(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let token = token_iter.peek();
-    if token.is_none() {
-        return false;
+        let end_of_line = tokens.get(5).unwrap();
+        assert_eq!(end_of_line.token_type, TokenType::EndOfLine);
+        assert_eq!(end_of_line.text, "\r\n");
+        assert_eq!(end_of_line.span.start.line, 1);
+        assert_eq!(end_of_line.span.end.line, 2);
+
+        let symbol = tokens.get(6).unwrap();
+        assert_eq!((symbol.span.start.offset, symbol.span.start.column), (9, 1));
     }
 
-    match token.unwrap().token_type {
-        TokenType::Symbol(_) => {
-            token_iter.next();
-            true
-        }
+    #[test]
+    fn span_columns_count_characters() {
+        let code = "\u{feff}t = \"žluťoučký\" + 1";
 
-        _ => false,
-    }
-}
+        let tokens = get_tokens(code);
 
-pub fn parse_equal_sign(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let next_token = token_iter.peek();
-    if next_token.unwrap().token_type == TokenType::EqualSign {
-        token_iter.next();
-        true
-    } else {
-        false
-    }
-}
+        // Byte order mark is kept as whitespace of no width
+        assert_eq!(tokens.first().unwrap().token_type, TokenType::Whitespace);
+        assert_eq!(tokens.get(1).unwrap().span.start.column, 1);
 
-pub fn parse_end_of_line(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let next_token = token_iter.peek();
-    if next_token.unwrap().token_type == TokenType::EndOfLine {
-        token_iter.next();
-        true
-    } else {
-        false
-    }
-}
+        let text = tokens.get(5).unwrap();
+        assert_eq!((text.span.start.column, text.span.end.column), (5, 16));
 
-pub fn parse_any_text(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let token = token_iter.peek();
-    if token.is_none() {
-        return false;
+        let operator = tokens.get(7).unwrap();
+        assert_eq!(operator.span.start.column, 17);
+        assert_eq!(
+            &code[operator.span.start.offset..operator.span.end.offset],
+            "+"
+        );
     }
 
-    match token.unwrap().token_type {
-        TokenType::Text(_) => {
-            token_iter.next();
-            true
-        }
+    #[test]
+    fn span_of_multiline_comment() {
+        let code = "a /* one\ntwo */ b";
 
-        _ => false,
-    }
-}
+        let tokens = get_tokens(code);
 
-pub fn is_last_on_line(
-    token_iter: &mut std::iter::Peekable<std::slice::Iter<'_, TokenInfo>>,
-) -> bool {
-    let next_token = token_iter.peek();
-    let token_type = &next_token.unwrap().token_type;
+        let comment = tokens.get(2).unwrap();
+        assert_eq!(comment.text, "/* one\ntwo */");
+        assert_eq!((comment.span.start.line, comment.span.start.column), (1, 3));
+        assert_eq!((comment.span.end.line, comment.span.end.column), (2, 7));
 
-    parse_whitespace(token_iter);
+        assert_eq!(tokens.get(4).unwrap().span.start.line, 2);
+    }
 
-    if *token_type == TokenType::EndOfLine {
-        return true;
+    #[test]
+    fn tokens_rebuild_source() {
+        let sources = [
+            "\u{feff}a = 1\r\n",
+            "a _\nb _ \r\nc _ ' some text \r\nd",
+            "x = \"open\r\n/* block\r\n comment */\tREM last\n\n",
+            "y\r = 1 /* never closed\r\nz _",
+        ];
+
+        for source in &sources {
+            let rebuilt: String = get_tokens(source)
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect();
+
+            assert_eq!(&rebuilt, source);
+        }
     }
 
-    match *token_type {
-        TokenType::Comment(_) => true,
+    #[test]
+    fn get_line_continuation() {
+        let code = "a _ ' note\r\nb";
+
+        let tokens = get_tokens(code);
 
-        _ => false,
+        assert_eq!(
+            tokens.get(2).unwrap().token_type,
+            TokenType::LineContinuation
+        );
+        assert_eq!(tokens.get(2).unwrap().text, "_ ' note\r\n");
+        assert_eq!(tokens.get(3).unwrap().span.start.line, 2);
     }
 }
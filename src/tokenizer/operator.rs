@@ -0,0 +1,66 @@
+/// Arithmetic, string, logical or bitwise operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    /// \ dividing without remainder
+    IntegerDivide,
+    Power,
+    Concatenate,
+    Mod,
+    And,
+    Or,
+    Xor,
+    Not,
+    Imp,
+    Eqv,
+    Shl,
+    Shr,
+}
+
+const WORD_OPERATORS: [(&str, Operator); 9] = [
+    ("AND", Operator::And),
+    ("EQV", Operator::Eqv),
+    ("IMP", Operator::Imp),
+    ("MOD", Operator::Mod),
+    ("NOT", Operator::Not),
+    ("OR", Operator::Or),
+    ("SHL", Operator::Shl),
+    ("SHR", Operator::Shr),
+    ("XOR", Operator::Xor),
+];
+
+impl Operator {
+    pub fn from_char(c: char) -> Option<Operator> {
+        match c {
+            '+' => Some(Operator::Add),
+            '-' => Some(Operator::Subtract),
+            '*' => Some(Operator::Multiply),
+            '/' => Some(Operator::Divide),
+            '\\' => Some(Operator::IntegerDivide),
+            '^' => Some(Operator::Power),
+            '&' => Some(Operator::Concatenate),
+            _ => None,
+        }
+    }
+
+    /// Looks up operator written as a word, such as MOD, by its uppercase name
+    pub fn from_word(name: &str) -> Option<Operator> {
+        WORD_OPERATORS
+            .iter()
+            .find(|(operator_name, _)| *operator_name == name)
+            .map(|&(_, operator)| operator)
+    }
+}
+
+/// Comparison other than =, which is kept as EqualSign since it assigns as well
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    NotEqual,
+}
@@ -0,0 +1,147 @@
+use super::{Keyword, Position, TokenInfo, TokenType};
+
+/// Walks the tokens of a script, the end of input is reported rather than panicking
+pub struct TokenCursor<'a> {
+    tokens: &'a [TokenInfo],
+    index: usize,
+}
+
+impl<'a> TokenCursor<'a> {
+    pub fn new(tokens: &'a [TokenInfo]) -> TokenCursor<'a> {
+        TokenCursor { tokens, index: 0 }
+    }
+
+    pub fn peek(&self) -> Option<&'a TokenInfo> {
+        self.tokens.get(self.index)
+    }
+
+    /// Start of the next token, or the end of input when there is none
+    pub fn position(&self) -> Position {
+        match (self.peek(), self.tokens.last()) {
+            (Some(token), _) => token.span.start,
+            (None, Some(last)) => last.span.end,
+            (None, None) => Position {
+                offset: 0,
+                line: 1,
+                column: 1,
+            },
+        }
+    }
+
+    fn parse_if(&mut self, predicate: impl Fn(&TokenType) -> bool) -> bool {
+        match self.peek() {
+            Some(token) if predicate(&token.token_type) => {
+                self.index += 1;
+                true
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Skips whitespace including line continuations, returns whether there was any
+    pub fn parse_whitespace(&mut self) -> bool {
+        let mut found = false;
+
+        while self.parse_if(|token_type| {
+            matches!(
+                token_type,
+                TokenType::Whitespace | TokenType::LineContinuation
+            )
+        }) {
+            found = true;
+        }
+
+        found
+    }
+
+    pub fn parse_keyword(&mut self, keyword: Keyword) -> bool {
+        self.parse_if(|token_type| *token_type == TokenType::Keyword(keyword))
+    }
+
+    /// Identifier is expected in uppercase
+    pub fn parse_identifier(&mut self, identifier: &str) -> bool {
+        self.parse_if(
+            |token_type| matches!(token_type, TokenType::Identifier(name) if name == identifier),
+        )
+    }
+
+    /// Keyword or identifier
+    pub fn parse_any_word(&mut self) -> bool {
+        self.parse_if(|token_type| {
+            matches!(token_type, TokenType::Keyword(_) | TokenType::Identifier(_))
+        })
+    }
+
+    pub fn parse_any_text(&mut self) -> bool {
+        self.parse_if(|token_type| matches!(token_type, TokenType::Text(_)))
+    }
+
+    pub fn parse_equal_sign(&mut self) -> bool {
+        self.parse_if(|token_type| *token_type == TokenType::EqualSign)
+    }
+
+    /// Skips whitespace and checks only a comment or the end of line or file follows
+    pub fn is_last_on_line(&mut self) -> bool {
+        self.parse_whitespace();
+
+        match self.peek() {
+            None => true,
+            Some(token) => matches!(
+                token.token_type,
+                TokenType::EndOfLine | TokenType::Comment(_)
+            ),
+        }
+    }
+}
+
+impl<'a> Iterator for TokenCursor<'a> {
+    type Item = &'a TokenInfo;
+
+    fn next(&mut self) -> Option<&'a TokenInfo> {
+        let token = self.peek()?;
+        self.index += 1;
+
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenCursor;
+    use crate::tokenizer::{get_tokens, Keyword};
+
+    #[test]
+    fn end_of_input_is_not_a_panic() {
+        let tokens = get_tokens("ALIAS _\n  DIM");
+        let mut cursor = TokenCursor::new(&tokens);
+
+        assert!(cursor.parse_keyword(Keyword::Alias));
+        assert!(cursor.parse_whitespace());
+        assert!(cursor.parse_any_word());
+
+        assert!(cursor.peek().is_none());
+        assert!(!cursor.parse_whitespace());
+        assert!(!cursor.parse_keyword(Keyword::As));
+        assert!(!cursor.parse_equal_sign());
+        assert!(cursor.is_last_on_line());
+        assert_eq!(cursor.next(), None);
+
+        let position = cursor.position();
+        assert_eq!((position.line, position.column), (2, 6));
+    }
+
+    #[test]
+    fn last_on_line_allows_comment() {
+        let tokens = get_tokens("a ' note\nb c");
+        let mut cursor = TokenCursor::new(&tokens);
+
+        assert!(cursor.parse_any_word());
+        assert!(cursor.is_last_on_line());
+
+        cursor.next(); // Comment
+        cursor.next(); // End of line
+        assert!(cursor.parse_any_word());
+        assert!(!cursor.is_last_on_line());
+    }
+}
@@ -0,0 +1,106 @@
+use super::{TokenInfo, TokenType};
+
+/// Significant token together with the trivia around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxToken {
+    /// Trivia between the previous line break and the token
+    pub leading_trivia: Vec<TokenInfo>,
+    pub token: TokenInfo,
+    /// Trivia after the token till the end of its line
+    pub trailing_trivia: Vec<TokenInfo>,
+}
+
+/// Lossless view of the token stream, where trivia belongs to significant tokens
+///
+/// End of line is significant, as it ends statements. Trivia following a token on
+/// the same line is trailing, anything else leads the next significant token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxTokens {
+    pub tokens: Vec<SyntaxToken>,
+    /// Trivia after the last significant token
+    pub end_trivia: Vec<TokenInfo>,
+}
+
+impl SyntaxTokens {
+    pub fn new(tokens: Vec<TokenInfo>) -> SyntaxTokens {
+        let mut syntax_tokens: Vec<SyntaxToken> = vec![];
+        let mut pending_trivia: Vec<TokenInfo> = vec![];
+
+        // Trailing trivia is collected only till the line ends
+        let mut line_open = false;
+
+        for token in tokens {
+            if !token.token_type.is_trivia() {
+                line_open = token.token_type != TokenType::EndOfLine;
+
+                syntax_tokens.push(SyntaxToken {
+                    leading_trivia: std::mem::take(&mut pending_trivia),
+                    token,
+                    trailing_trivia: vec![],
+                });
+            } else if line_open {
+                line_open = !token.text.contains('\n');
+
+                if let Some(last) = syntax_tokens.last_mut() {
+                    last.trailing_trivia.push(token);
+                }
+            } else {
+                pending_trivia.push(token);
+            }
+        }
+
+        SyntaxTokens {
+            tokens: syntax_tokens,
+            end_trivia: pending_trivia,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyntaxTokens;
+    use crate::tokenizer::{get_tokens, TokenType};
+
+    #[test]
+    fn trivia_is_attached_to_tokens() {
+        let code = "' header\r\n  a = 1 ' one _\r\n\r\nb _ ' more\r\n  + 2 /* end */";
+
+        let syntax_tokens = SyntaxTokens::new(get_tokens(code));
+
+        let a = &syntax_tokens.tokens[1];
+        assert_eq!(a.token.text, "a");
+        assert_eq!(a.leading_trivia.len(), 1);
+        assert_eq!(a.leading_trivia[0].text, "  ");
+        assert_eq!(a.trailing_trivia[0].text, " ");
+
+        let one = &syntax_tokens.tokens[3];
+        assert_eq!(one.token.text, "1");
+        assert_eq!(one.trailing_trivia.len(), 2);
+        assert_eq!(one.trailing_trivia[1].text, "' one _");
+
+        let b = &syntax_tokens.tokens[6];
+        assert_eq!(b.token.text, "b");
+        assert_eq!(b.trailing_trivia[1].token_type, TokenType::LineContinuation);
+
+        let plus = &syntax_tokens.tokens[7];
+        assert_eq!(plus.leading_trivia[0].text, "  ");
+
+        assert!(syntax_tokens.end_trivia.is_empty());
+
+        // Nothing is lost, the tokens with their trivia give the source back
+        let source: String = syntax_tokens
+            .tokens
+            .iter()
+            .flat_map(|token| {
+                token
+                    .leading_trivia
+                    .iter()
+                    .chain(Some(&token.token))
+                    .chain(token.trailing_trivia.iter())
+            })
+            .chain(syntax_tokens.end_trivia.iter())
+            .map(|token| token.text.as_str())
+            .collect();
+        assert_eq!(source, code);
+    }
+}